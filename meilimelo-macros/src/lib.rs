@@ -2,7 +2,7 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
-use syn::{parse_macro_input, ItemStruct};
+use syn::{parse_macro_input, Attribute, Field, ItemStruct, Lit, Meta, NestedMeta};
 
 /// Macro to mark a struct as a schema to be used with `meilimelo`
 ///
@@ -10,6 +10,11 @@ use syn::{parse_macro_input, ItemStruct};
 /// `_formatted` field. MeiliSearch uses this field to provide augmented data
 /// in the results (highlights, crops, etc.).
 ///
+/// Since MeiliSearch may omit attributes from `_formatted`, or change their
+/// type (numbers being turned into strings, for example), every field of the
+/// formatted struct is optional, and is set to `None` when missing or when
+/// its value cannot be deserialized into the original type.
+///
 /// # Example
 ///
 /// ```
@@ -22,13 +27,18 @@ use syn::{parse_macro_input, ItemStruct};
 /// }
 /// ```
 ///
+/// An accessor is also generated for each field, returning its formatted
+/// value if there is one, or its raw value otherwise, as a
+/// `meilimelo::Formatted`.
+///
 /// The above struct renders as follows:
 ///
 /// ```ignore
 /// #[derive(Debug, Default, Serialize, Deserialize)]
+/// #[serde(default)]
 /// struct FormattedEmployee {
-///   firstname: String,
-///   lastname: String
+///   firstname: Option<String>,
+///   lastname: Option<String>
 /// }
 ///
 /// #[derive(Debug, Default, Serialize, Deserialize)]
@@ -38,12 +48,40 @@ use syn::{parse_macro_input, ItemStruct};
 ///   #[serde(rename = "_formatted")]
 ///   formatted: FormattedEmployee
 /// }
+///
+/// impl Employee {
+///   fn formatted_firstname(&self) -> meilimelo::Formatted<'_, String> { /* ... */ }
+///   fn formatted_lastname(&self) -> meilimelo::Formatted<'_, String> { /* ... */ }
+/// }
+/// ```
+///
+/// A companion struct holding the name of each attribute as a constant is
+/// also generated, so attribute names can be checked at compile time when
+/// building queries. Names set through `#[serde(rename = "...")]` on fields
+/// and `#[serde(rename_all = "...")]` on the struct are honored:
+///
+/// ```ignore
+/// struct EmployeeFields;
+///
+/// impl EmployeeFields {
+///   pub const FIRSTNAME: &'static str = "firstname";
+///   pub const LASTNAME: &'static str = "lastname";
+/// }
+///
+/// meili.search("employees").retrieve(&[EmployeeFields::FIRSTNAME]);
 /// ```
 #[proc_macro_attribute]
 pub fn schema(_attribute: TokenStream, item: TokenStream) -> TokenStream {
   let input = parse_macro_input!(item as ItemStruct);
   let name = &input.ident;
 
+  let (rename_all, rule) = match rename_all(&input.attrs) {
+    Ok(rename_all) => rename_all,
+    Err(err) => return TokenStream::from(err.to_compile_error()),
+  };
+
+  let rename_all = rename_all.map(|meta| quote! { #[serde(#meta)] });
+
   let fields = input.fields.iter().map(|field| {
     quote! {
       #field,
@@ -51,10 +89,49 @@ pub fn schema(_attribute: TokenStream, item: TokenStream) -> TokenStream {
   });
 
   let formatted_name = format_ident!("Formatted{}", name);
-  let formatted_fields = fields.clone();
+  let formatted_fields = input.fields.iter().map(|field| {
+    let attrs = formatted_attrs(field);
+    let vis = &field.vis;
+    let ident = &field.ident;
+    let ty = &field.ty;
+
+    quote! {
+      #(#attrs)*
+      #[serde(deserialize_with = "meilimelo::__private::lenient")]
+      #vis #ident: Option<#ty>,
+    }
+  });
+
+  let accessors = input.fields.iter().filter_map(|field| {
+    let ident = field.ident.as_ref()?;
+    let vis = &field.vis;
+    let ty = &field.ty;
+    let accessor = format_ident!("formatted_{}", ident);
+
+    Some(quote! {
+      #vis fn #accessor(&self) -> meilimelo::Formatted<'_, #ty> {
+        meilimelo::Formatted::new(
+          self.formatted.as_ref().and_then(|formatted| formatted.#ident.as_ref()),
+          &self.#ident,
+        )
+      }
+    })
+  });
+
+  let fields_name = format_ident!("{}Fields", name);
+  let constants = input.fields.iter().filter_map(|field| {
+    let ident = field.ident.as_ref()?;
+    let constant = format_ident!("{}", ident.to_string().trim_start_matches("r#").to_uppercase());
+    let attribute = serialized_name(field, rule.as_deref())?;
+
+    Some(quote! {
+      pub const #constant: &'static str = #attribute;
+    })
+  });
 
   let output = quote! {
     #[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+    #rename_all
     struct #name {
       #(
         #fields
@@ -64,14 +141,181 @@ pub fn schema(_attribute: TokenStream, item: TokenStream) -> TokenStream {
     }
 
     #[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+    #[serde(default)]
+    #rename_all
     struct #formatted_name {
       #(
         #formatted_fields
       )*
     }
 
+    #[allow(dead_code)]
+    impl #name {
+      #(
+        #accessors
+      )*
+    }
+
     impl meilimelo::Schema for #name {}
+
+    #[allow(dead_code)]
+    struct #fields_name;
+
+    #[allow(dead_code)]
+    impl #fields_name {
+      #(
+        #constants
+      )*
+    }
   };
 
   TokenStream::from(output)
 }
+
+/// Attributes of a field, keeping only the serde attributes that rename it
+///
+/// Formatted fields are optional and always deserialized through `lenient`,
+/// so attributes expecting the original type (`with`, `default = "..."`,
+/// `skip_serializing_if`, etc.) cannot be copied.
+fn formatted_attrs(field: &Field) -> Vec<proc_macro2::TokenStream> {
+  field
+    .attrs
+    .iter()
+    .filter_map(|attr| {
+      if !attr.path.is_ident("serde") {
+        return Some(quote! { #attr });
+      }
+
+      let list = match attr.parse_meta() {
+        Ok(Meta::List(list)) => list,
+        _ => return None,
+      };
+
+      let nested: Vec<_> = list
+        .nested
+        .into_iter()
+        .filter(|nested| match nested {
+          NestedMeta::Meta(meta) => meta.path().is_ident("rename") || meta.path().is_ident("alias"),
+          _ => false,
+        })
+        .collect();
+
+      if nested.is_empty() {
+        None
+      } else {
+        Some(quote! { #[serde(#(#nested),*)] })
+      }
+    })
+    .collect()
+}
+
+/// Container-level `rename_all` attribute, and the rule it applies when serializing
+fn rename_all(attrs: &[Attribute]) -> syn::Result<(Option<NestedMeta>, Option<String>)> {
+  let meta = attrs
+    .iter()
+    .filter(|attr| attr.path.is_ident("serde"))
+    .filter_map(|attr| match attr.parse_meta() {
+      Ok(Meta::List(list)) => Some(list.nested),
+      _ => None,
+    })
+    .flatten()
+    .find(|nested| matches!(nested, NestedMeta::Meta(meta) if meta.path().is_ident("rename_all")));
+
+  let rule = match meta {
+    Some(NestedMeta::Meta(ref meta)) => renamed(meta)?,
+    _ => None,
+  };
+
+  if let Some(ref rule) = rule {
+    apply_rule(rule, "").ok_or_else(|| syn::Error::new_spanned(&meta, format!("unknown rename rule `{}`", rule)))?;
+  }
+
+  Ok((meta, rule))
+}
+
+/// Name set by a `rename` or `rename_all` attribute for serialization
+///
+/// Both `rename = "..."` and `rename(serialize = "...")` are supported.
+fn renamed(meta: &Meta) -> syn::Result<Option<String>> {
+  match meta {
+    Meta::NameValue(value) => match value.lit {
+      Lit::Str(ref name) => Ok(Some(name.value())),
+      _ => Err(syn::Error::new_spanned(&value.lit, "expected a string")),
+    },
+
+    Meta::List(list) => Ok(list.nested.iter().find_map(|nested| match nested {
+      NestedMeta::Meta(Meta::NameValue(value)) if value.path.is_ident("serialize") => match value.lit {
+        Lit::Str(ref name) => Some(name.value()),
+        _ => None,
+      },
+      _ => None,
+    })),
+
+    Meta::Path(_) => Ok(None),
+  }
+}
+
+/// Applies a serde `rename_all` rule to a snake_case field name
+fn apply_rule(rule: &str, field: &str) -> Option<String> {
+  let pascal = || {
+    field
+      .split('_')
+      .map(|word| {
+        let mut chars = word.chars();
+
+        match chars.next() {
+          Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+          None => String::new(),
+        }
+      })
+      .collect::<String>()
+  };
+
+  match rule {
+    "lowercase" | "snake_case" => Some(field.to_string()),
+    "UPPERCASE" | "SCREAMING_SNAKE_CASE" => Some(field.to_ascii_uppercase()),
+    "PascalCase" => Some(pascal()),
+    "camelCase" => {
+      let pascal = pascal();
+      let mut chars = pascal.chars();
+
+      Some(match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => String::new(),
+      })
+    }
+    "kebab-case" => Some(field.replace('_', "-")),
+    "SCREAMING-KEBAB-CASE" => Some(field.to_ascii_uppercase().replace('_', "-")),
+    _ => None,
+  }
+}
+
+/// Name of the attribute a field is serialized to
+///
+/// Honors `#[serde(rename = "...")]` on the field, then the `rename_all`
+/// rule of the container.
+fn serialized_name(field: &Field, rule: Option<&str>) -> Option<String> {
+  let rename = field
+    .attrs
+    .iter()
+    .filter(|attr| attr.path.is_ident("serde"))
+    .filter_map(|attr| match attr.parse_meta() {
+      Ok(Meta::List(list)) => Some(list.nested),
+      _ => None,
+    })
+    .flatten()
+    .find_map(|nested| match nested {
+      NestedMeta::Meta(ref meta) if meta.path().is_ident("rename") => renamed(meta).ok().flatten(),
+      _ => None,
+    });
+
+  rename.or_else(|| {
+    let ident = field.ident.as_ref()?.to_string();
+    let ident = ident.trim_start_matches("r#");
+
+    match rule {
+      Some(rule) => apply_rule(rule, ident),
+      None => Some(ident.to_string()),
+    }
+  })
+}
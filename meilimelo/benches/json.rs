@@ -0,0 +1,71 @@
+//! Deserialization of a large search response with each JSON backend
+//!
+//! Run with `cargo bench --features simd-json` to compare `serde_json`
+//! with `simd-json`.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use meilimelo::prelude::*;
+
+#[meilimelo::schema]
+struct Employee {
+  id: i64,
+  firstname: String,
+  lastname: String,
+  bio: String,
+  skills: Vec<String>,
+}
+
+/// Builds a response body holding `count` hits, with their formatted values
+fn response(count: usize) -> Vec<u8> {
+  let hits: Vec<_> = (0..count)
+    .map(|id| {
+      serde_json::json!({
+        "id": id,
+        "firstname": "Luke",
+        "lastname": "Skywalker",
+        "bio": "Luke Skywalker is a Jedi knight from Tatooine who fought for the Rebel Alliance",
+        "skills": ["piloting", "lightsaber", "the force"],
+        "_formatted": {
+          "firstname": "<em>Luke</em>",
+          "bio": "…is a <em>Jedi</em> knight…",
+        },
+      })
+    })
+    .collect();
+
+  serde_json::to_vec(&serde_json::json!({
+    "hits": hits,
+    "query": "jedi",
+    "exhaustiveNbHits": false,
+    "nbHits": count,
+    "limit": count,
+    "offset": 0,
+    "processingTimeMs": 12,
+  }))
+  .unwrap()
+}
+
+fn deserialize(c: &mut Criterion) {
+  let body = response(10_000);
+  let mut group = c.benchmark_group("results");
+
+  group.throughput(Throughput::Bytes(body.len() as u64));
+
+  group.bench_function("serde_json", |b| {
+    b.iter(|| serde_json::from_slice::<Results<Employee>>(&body).unwrap())
+  });
+
+  #[cfg(feature = "simd-json")]
+  group.bench_function("simd_json", |b| {
+    b.iter_batched(
+      || body.clone(),
+      |mut body| simd_json::serde::from_slice::<Results<Employee>>(&mut body).unwrap(),
+      criterion::BatchSize::LargeInput,
+    )
+  });
+
+  group.finish();
+}
+
+criterion_group!(benches, deserialize);
+criterion_main!(benches);
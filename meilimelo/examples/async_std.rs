@@ -0,0 +1,19 @@
+use meilimelo::prelude::*;
+
+#[meilimelo::schema]
+struct Employee {
+  firstname: String,
+  lastname: String,
+}
+
+#[async_std::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+  let meili = MeiliMelo::new("http://meilisearch.example.com:7700").with_secret_key("abcdef");
+  let people = meili.search("persons").query("johnson").run::<Employee>().await?;
+
+  for person in &people {
+    println!("{} {}", person.firstname, person.lastname);
+  }
+
+  Ok(())
+}
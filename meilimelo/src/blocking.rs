@@ -0,0 +1,155 @@
+use std::{io::Read, vec::IntoIter};
+
+use reqwest::{
+  blocking::{Client, Response},
+  StatusCode,
+};
+use serde::Deserialize;
+
+use crate::{documents, prelude::*, search::QueryError, Error};
+
+/// Blocking iterator over all the documents of an index
+///
+/// Documents are fetched lazily, one page at a time, as the iterator is
+/// consumed. Iteration stops after the first error. Since it performs
+/// blocking requests, it must not be used from within an async runtime.
+pub struct DocumentsIter<'m, R> {
+  meili: &'m MeiliMelo<'m>,
+  client: Client,
+  index: String,
+  page_size: i64,
+  offset: i64,
+  page: IntoIter<R>,
+  done: bool,
+}
+
+impl<'m, R> DocumentsIter<'m, R>
+where
+  for<'de> R: Deserialize<'de>,
+{
+  pub(crate) fn new(meili: &'m MeiliMelo<'m>, index: &str, page_size: i64) -> DocumentsIter<'m, R> {
+    DocumentsIter {
+      meili,
+      client: meili.config.build_blocking(),
+      index: index.to_string(),
+      // MeiliSearch never returns more documents than this in a single page
+      page_size: page_size.min(documents::MAX_DOCUMENTS_PER_PAGE),
+      offset: 0,
+      page: Vec::new().into_iter(),
+      done: false,
+    }
+  }
+
+  fn fetch(&self) -> Result<Vec<R>, Error> {
+    let url = format!(
+      "{}{}",
+      self.meili.host,
+      documents::list_path(&self.index, self.page_size, self.offset)
+    );
+
+    let request = self
+      .meili
+      .headers(self.meili.secret_key.as_deref())
+      .into_iter()
+      .fold(self.client.get(&url), |request, (name, value)| {
+        request.header(name, value)
+      });
+
+    let response = request.send().map_err(|err| Error::UpstreamError(err))?;
+
+    match response.status() {
+      StatusCode::OK => self.json::<Vec<R>>(response),
+
+      _ => {
+        let error = self.json::<QueryError>(response)?;
+
+        Err(Error::InvalidQuery(error))
+      }
+    }
+  }
+
+  fn json<T>(&self, response: Response) -> Result<T, Error>
+  where
+    for<'de> T: Deserialize<'de>,
+  {
+    #[allow(unused_mut)]
+    let mut body = match self.meili.config.max_response_bytes {
+      Some(max) => read_limited(response, max)?,
+      None => response.bytes().map_err(|err| Error::UpstreamError(err))?.to_vec(),
+    };
+
+    #[cfg(not(feature = "simd-json"))]
+    let document = serde_json::from_slice::<T>(&body);
+
+    #[cfg(feature = "simd-json")]
+    let document = simd_json::serde::from_slice::<T>(&mut body);
+
+    document.map_err(|err| Error::ParseError(Box::new(err)))
+  }
+}
+
+/// Reads the body of a blocking response, failing if it exceeds `max` bytes
+fn read_limited(response: Response, max: usize) -> Result<Vec<u8>, Error> {
+  if let Some(length) = response.content_length() {
+    if length > max as u64 {
+      return Err(Error::ResponseTooLarge(max));
+    }
+  }
+
+  let mut body = Vec::new();
+
+  response
+    .take(max as u64 + 1)
+    .read_to_end(&mut body)
+    .map_err(|err| Error::ParseError(Box::new(err)))?;
+
+  if body.len() > max {
+    return Err(Error::ResponseTooLarge(max));
+  }
+
+  Ok(body)
+}
+
+impl<'m, R> Iterator for DocumentsIter<'m, R>
+where
+  for<'de> R: Deserialize<'de>,
+{
+  type Item = Result<R, Error>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if let Some(document) = self.page.next() {
+      return Some(Ok(document));
+    }
+
+    if self.done {
+      return None;
+    }
+
+    match self.fetch() {
+      Ok(page) => {
+        self.done = page.is_empty() || (page.len() as i64) < self.page_size;
+        self.offset += page.len() as i64;
+        self.page = page.into_iter();
+        self.page.next().map(Ok)
+      }
+
+      Err(err) => {
+        self.done = true;
+        Some(Err(err))
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::prelude::*;
+
+  #[test]
+  fn page_size() {
+    let meili = MeiliMelo::new("http://localhost:7700");
+    let iter = meili.documents_iter::<serde_json::Value>("employees", 5000);
+
+    assert_eq!(iter.page_size, 1000);
+  }
+}
@@ -0,0 +1,176 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+
+use reqwest::{header::CONTENT_TYPE, Client, RequestBuilder, Response};
+use serde::Serialize;
+
+use crate::{Error, MeiliMelo};
+
+/// Settings used to build the HTTP client shared by a `MeiliMelo` instance
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ClientConfig {
+  /// Maximum number of idle connections kept per host
+  #[cfg(not(target_arch = "wasm32"))]
+  pub(crate) pool_max_idle: Option<usize>,
+  /// Duration after which idle connections are closed
+  #[cfg(not(target_arch = "wasm32"))]
+  pub(crate) pool_idle_timeout: Option<Duration>,
+  /// Maximum duration to establish a connection
+  #[cfg(not(target_arch = "wasm32"))]
+  pub(crate) connect_timeout: Option<Duration>,
+  /// Maximum duration to receive a complete response
+  #[cfg(not(target_arch = "wasm32"))]
+  pub(crate) read_timeout: Option<Duration>,
+  /// Maximum size of a response body
+  #[cfg(not(target_arch = "wasm32"))]
+  pub(crate) max_response_bytes: Option<usize>,
+  /// Whether to measure the duration of searches
+  #[cfg(not(target_arch = "wasm32"))]
+  pub(crate) timing: bool,
+  /// Whether to accept invalid TLS certificates
+  #[cfg(all(feature = "insecure-tls", not(target_arch = "wasm32")))]
+  pub(crate) accept_invalid_certs: bool,
+}
+
+impl ClientConfig {
+  /// Builds an HTTP client from the configuration
+  ///
+  /// # Panics
+  ///
+  /// Like `reqwest::Client::new()`, this panics if the TLS backend cannot be
+  /// initialized.
+  pub(crate) fn build(&self) -> Client {
+    #[allow(unused_mut)]
+    let mut builder = Client::builder();
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+      if let Some(max) = self.pool_max_idle {
+        builder = builder.pool_max_idle_per_host(max);
+      }
+
+      if let Some(timeout) = self.pool_idle_timeout {
+        builder = builder.pool_idle_timeout(timeout);
+      }
+
+      if let Some(timeout) = self.connect_timeout {
+        builder = builder.connect_timeout(timeout);
+      }
+
+      if let Some(timeout) = self.read_timeout {
+        builder = builder.timeout(timeout);
+      }
+    }
+
+    // Advertise the supported encodings through `Accept-Encoding` and
+    // transparently decompress the responses.
+    #[cfg(all(feature = "gzip", not(target_arch = "wasm32")))]
+    {
+      builder = builder.gzip(true);
+    }
+
+    #[cfg(all(feature = "brotli", not(target_arch = "wasm32")))]
+    {
+      builder = builder.brotli(true);
+    }
+
+    #[cfg(all(feature = "insecure-tls", not(target_arch = "wasm32")))]
+    {
+      builder = builder.danger_accept_invalid_certs(self.accept_invalid_certs);
+    }
+
+    builder.build().expect("could not build HTTP client")
+  }
+
+  /// Builds a blocking HTTP client from the configuration
+  ///
+  /// # Panics
+  ///
+  /// Like `reqwest::blocking::Client::new()`, this panics if the TLS backend
+  /// cannot be initialized, or if it is called from within an async runtime.
+  #[cfg(feature = "blocking")]
+  pub(crate) fn build_blocking(&self) -> reqwest::blocking::Client {
+    let mut builder = reqwest::blocking::Client::builder();
+
+    if let Some(max) = self.pool_max_idle {
+      builder = builder.pool_max_idle_per_host(max);
+    }
+
+    if let Some(timeout) = self.pool_idle_timeout {
+      builder = builder.pool_idle_timeout(timeout);
+    }
+
+    if let Some(timeout) = self.connect_timeout {
+      builder = builder.connect_timeout(timeout);
+    }
+
+    if let Some(timeout) = self.read_timeout {
+      builder = builder.timeout(timeout);
+    }
+
+    #[cfg(feature = "gzip")]
+    {
+      builder = builder.gzip(true);
+    }
+
+    #[cfg(feature = "brotli")]
+    {
+      builder = builder.brotli(true);
+    }
+
+    #[cfg(feature = "insecure-tls")]
+    {
+      builder = builder.danger_accept_invalid_certs(self.accept_invalid_certs);
+    }
+
+    builder.build().expect("could not build HTTP client")
+  }
+}
+
+/// Attaches JSON bodies to requests, with the `Content-Type` configured on a descriptor
+pub(crate) trait JsonBody {
+  fn json_body<T>(self, meili: &MeiliMelo<'_>, body: &T) -> Self
+  where
+    T: Serialize + ?Sized;
+}
+
+impl JsonBody for RequestBuilder {
+  fn json_body<T>(self, meili: &MeiliMelo<'_>, body: &T) -> RequestBuilder
+  where
+    T: Serialize + ?Sized,
+  {
+    // `RequestBuilder::json()` always sets `Content-Type` to `application/json`,
+    // so the body is serialized here when another content type is configured.
+    match meili.content_type {
+      Some(content_type) => match serde_json::to_vec(body) {
+        Ok(body) => self.header(CONTENT_TYPE, content_type).body(body),
+        // Let the HTTP client report the serialization error when the request is sent
+        Err(_) => self.json(body),
+      },
+
+      None => self.json(body),
+    }
+  }
+}
+
+/// Reads the body of a response, failing as soon as it exceeds `max` bytes
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn read_limited(mut response: Response, max: usize) -> Result<Vec<u8>, Error> {
+  if let Some(length) = response.content_length() {
+    if length > max as u64 {
+      return Err(Error::ResponseTooLarge(max));
+    }
+  }
+
+  let mut body = Vec::new();
+
+  while let Some(chunk) = response.chunk().await.map_err(|err| Error::UpstreamError(err))? {
+    if body.len() + chunk.len() > max {
+      return Err(Error::ResponseTooLarge(max));
+    }
+
+    body.extend_from_slice(&chunk);
+  }
+
+  Ok(body)
+}
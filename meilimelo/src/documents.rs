@@ -1,49 +1,398 @@
-use reqwest::Method;
+use futures::{Stream, StreamExt};
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use reqwest::{
+  header::{CONTENT_TYPE, ETAG, IF_NONE_MATCH},
+  Method, StatusCode,
+};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-use crate::{prelude::*, Error};
+use crate::{
+  client::JsonBody,
+  filters::escape_filter_value,
+  indices,
+  prelude::*,
+  search::QueryError,
+  updates::{Enqueued, Update, UpdateKind},
+  Error,
+};
 
-/// Descriptor for an asynchronous upstream operation
+/// Characters to be percent-encoded in a URL path segment
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+  .add(b' ')
+  .add(b'"')
+  .add(b'#')
+  .add(b'%')
+  .add(b'/')
+  .add(b'<')
+  .add(b'>')
+  .add(b'?')
+  .add(b'`')
+  .add(b'{')
+  .add(b'}');
+
+/// How documents sharing their primary key with existing ones are handled
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InsertMode {
+  /// Existing documents are replaced by the new ones (sent with `POST`)
+  Replace,
+  /// Existing documents are updated with the attributes of the new ones, keeping the others (sent with `PUT`)
+  Update,
+}
+
+/// Partial update of a single document, identified by the value of its primary key
+///
+/// # Examples
+///
+/// ```
+/// use meilimelo::PartialUpdate;
+///
+/// let update = PartialUpdate::new("lskywalker").set("status", "archived");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialUpdate {
+  /// Value of the primary key of the document
+  pub id: Value,
+  /// Attributes to set on the document
+  pub fields: Map<String, Value>,
+}
+
+impl PartialUpdate {
+  pub fn new<I>(id: I) -> PartialUpdate
+  where
+    I: Into<Value>,
+  {
+    PartialUpdate {
+      id: id.into(),
+      fields: Map::new(),
+    }
+  }
+
+  /// Sets an attribute of the document to the given value
+  pub fn set<V>(mut self, field: &str, value: V) -> PartialUpdate
+  where
+    V: Into<Value>,
+  {
+    self.fields.insert(field.to_string(), value.into());
+    self
+  }
+
+  fn into_document(self, primary_key: &str) -> Value {
+    let mut document = self.fields;
+
+    document.insert(primary_key.to_string(), self.id);
+
+    Value::Object(document)
+  }
+}
+
+/// Parameters to a document fetch
+///
+/// Unset parameters are not sent, and default to MeiliSearch's defaults.
+#[derive(Debug, Default, Serialize)]
+pub struct DocumentsFetch<'a> {
+  /// Number of documents to return
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub limit: Option<i64>,
+  /// Number of documents to skip
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub offset: Option<i64>,
+  /// Attributes to return for each document
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub fields: Option<&'a [&'a str]>,
+  /// Filter expression documents must match
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub filter: Option<&'a str>,
+}
+
+/// Page of documents returned by a document fetch
 #[derive(Debug, Deserialize)]
-pub struct Update {
-  #[serde(rename = "updateId")]
-  pub id: i64,
+pub struct DocumentsPage<R> {
+  pub results: Vec<R>,
+  pub limit: i64,
+  pub offset: i64,
+  pub total: i64,
+}
+
+/// Document returned by a conditional read, alongside its entity tag
+#[derive(Debug)]
+pub struct TaggedDocument<R> {
+  pub document: R,
+  /// Value of the `ETag` header returned by MeiliSearch, if any
+  pub etag: Option<String>,
 }
 
-pub(crate) async fn insert<T>(meili: &MeiliMelo<'_>, index: &str, documents: &Vec<T>) -> Result<Update, Error>
+pub(crate) async fn insert<T>(meili: &MeiliMelo<'_>, index: &str, documents: &[T]) -> Result<Update, Error>
 where
   T: Serialize,
 {
   let response = meili
     .request(Method::POST, &format!("/indexes/{}/documents", index))
-    .json(&documents)
+    .json_body(meili, &documents)
     .send()
     .await
-    .map_err(|err| Error::UpstreamError(err))?
-    .json::<Update>()
-    .await
     .map_err(|err| Error::UpstreamError(err))?;
 
-  Ok(response)
+  match response.status() {
+    status if status.is_success() => Ok(
+      meili
+        .json::<Enqueued>(response)
+        .await?
+        .into_update(index, UpdateKind::DocumentsAddition),
+    ),
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(insertion_error(error))
+    }
+  }
+}
+
+pub(crate) async fn insert_stream<T, S>(
+  meili: &MeiliMelo<'_>,
+  index: &str,
+  documents: S,
+  batch_size: usize,
+) -> Result<Vec<Update>, Error>
+where
+  T: Serialize,
+  S: Stream<Item = T>,
+{
+  if batch_size == 0 {
+    return Err(Error::ValidationError(
+      "batch size must be greater than zero".to_string(),
+    ));
+  }
+
+  let mut batches = Box::pin(documents.chunks(batch_size));
+  let mut updates = Vec::new();
+
+  while let Some(batch) = batches.next().await {
+    let mut body = Vec::new();
+
+    for document in &batch {
+      serde_json::to_writer(&mut body, document).map_err(|err| Error::SerializeError(Box::new(err)))?;
+      body.push(b'\n');
+    }
+
+    let response = meili
+      .request(Method::POST, &format!("/indexes/{}/documents", index))
+      .header(CONTENT_TYPE, "application/x-ndjson")
+      .body(body)
+      .send()
+      .await
+      .map_err(|err| Error::UpstreamError(err))?;
+
+    match response.status() {
+      status if status.is_success() => updates.push(
+        meili
+          .json::<Enqueued>(response)
+          .await?
+          .into_update(index, UpdateKind::DocumentsAddition),
+      ),
+
+      _ => {
+        let error = meili.json::<QueryError>(response).await?;
+
+        return Err(insertion_error(error));
+      }
+    }
+  }
+
+  Ok(updates)
+}
+
+pub(crate) async fn update<T>(
+  meili: &MeiliMelo<'_>,
+  index: &str,
+  documents: &[T],
+  primary_key: Option<&str>,
+) -> Result<Update, Error>
+where
+  T: Serialize,
+{
+  let mut request = meili
+    .request(Method::PUT, &format!("/indexes/{}/documents", index))
+    .json_body(meili, &documents);
+
+  if let Some(primary_key) = primary_key {
+    request = request.query(&[("primaryKey", primary_key)]);
+  }
+
+  let response = request.send().await.map_err(|err| Error::UpstreamError(err))?;
+
+  match response.status() {
+    status if status.is_success() => Ok(
+      meili
+        .json::<Enqueued>(response)
+        .await?
+        .into_update(index, UpdateKind::DocumentsPartial),
+    ),
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(insertion_error(error))
+    }
+  }
+}
+
+fn insertion_error(error: QueryError) -> Error {
+  match error.code.as_str() {
+    "missing_primary_key" | "index_primary_key_no_candidate_found" => Error::MissingPrimaryKey(error),
+    _ => Error::InvalidQuery(error),
+  }
+}
+
+fn document_path(index: &str, uid: &str) -> String {
+  format!(
+    "/indexes/{}/documents/{}",
+    index,
+    utf8_percent_encode(uid, PATH_SEGMENT)
+  )
 }
 
+/// Maximum number of documents returned by MeiliSearch in a single page
+pub(crate) const MAX_DOCUMENTS_PER_PAGE: i64 = 1000;
+
+pub(crate) fn list_path(index: &str, limit: i64, offset: i64) -> String {
+  format!("/indexes/{}/documents?limit={}&offset={}", index, limit, offset)
+}
+
+/// Lists documents, splitting the request into pages MeiliSearch can return
 pub(crate) async fn list<R>(meili: &MeiliMelo<'_>, index: &str, limit: i64, offset: i64) -> Result<Vec<R>, Error>
+where
+  for<'de> R: Deserialize<'de>,
+{
+  let mut documents = Vec::new();
+  let mut received = Vec::new();
+
+  while let Some((size, offset)) = next_page(limit, offset, &received) {
+    let page = list_page::<R>(meili, index, size, offset).await?;
+
+    received.push(page.len() as i64);
+    documents.extend(page);
+  }
+
+  Ok(documents)
+}
+
+/// Size and offset of the next page to request, given the size of the pages already received
+///
+/// Pages are capped at the maximum MeiliSearch returns, and no page is
+/// requested once `limit` documents were received or a page came back
+/// short, which means the end of the index was reached.
+fn next_page(limit: i64, offset: i64, received: &[i64]) -> Option<(i64, i64)> {
+  let mut offset = offset;
+  let mut remaining = limit;
+
+  for &count in received {
+    if count < remaining.min(MAX_DOCUMENTS_PER_PAGE) {
+      return None;
+    }
+
+    offset += count;
+    remaining -= count;
+  }
+
+  if remaining > 0 {
+    Some((remaining.min(MAX_DOCUMENTS_PER_PAGE), offset))
+  } else {
+    None
+  }
+}
+
+async fn list_page<R>(meili: &MeiliMelo<'_>, index: &str, limit: i64, offset: i64) -> Result<Vec<R>, Error>
 where
   for<'de> R: Deserialize<'de>,
 {
   let response = meili
-    .request(
-      Method::GET,
-      &format!("/indexes/{}/documents?limit={}&offset={}", index, limit, offset),
-    )
+    .request(Method::GET, &list_path(index, limit, offset))
     .send()
     .await
-    .map_err(|err| Error::UpstreamError(err))?
-    .json::<Vec<R>>()
+    .map_err(|err| Error::UpstreamError(err))?;
+
+  meili.json::<Vec<R>>(response).await
+}
+
+pub(crate) async fn list_with<R>(meili: &MeiliMelo<'_>, index: &str, params: &[(&str, &str)]) -> Result<Vec<R>, Error>
+where
+  for<'de> R: Deserialize<'de>,
+{
+  let response = meili
+    .request(Method::GET, &format!("/indexes/{}/documents", index))
+    .query(params)
+    .send()
     .await
     .map_err(|err| Error::UpstreamError(err))?;
 
-  Ok(response)
+  meili.json::<Vec<R>>(response).await
+}
+
+pub(crate) async fn fetch<R>(
+  meili: &MeiliMelo<'_>,
+  index: &str,
+  query: &DocumentsFetch<'_>,
+) -> Result<DocumentsPage<R>, Error>
+where
+  for<'de> R: Deserialize<'de>,
+{
+  let response = meili
+    .request(Method::POST, &format!("/indexes/{}/documents/fetch", index))
+    .json_body(meili, query)
+    .send()
+    .await
+    .map_err(|err| Error::UpstreamError(err))?;
+
+  match response.status() {
+    StatusCode::OK => meili.json::<DocumentsPage<R>>(response).await,
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(Error::InvalidQuery(error))
+    }
+  }
+}
+
+pub(crate) async fn patch(meili: &MeiliMelo<'_>, index: &str, updates: &[PartialUpdate]) -> Result<Update, Error> {
+  let primary_key = primary_key(meili, index).await?;
+  let documents: Vec<Value> = updates
+    .iter()
+    .cloned()
+    .map(|update| update.into_document(&primary_key))
+    .collect();
+
+  update(meili, index, &documents, None).await
+}
+
+async fn primary_key(meili: &MeiliMelo<'_>, index: &str) -> Result<String, Error> {
+  indices::get(meili, index)
+    .await?
+    .primary_key
+    .ok_or_else(|| Error::ValidationError(format!("index {} has no primary key", index)))
+}
+
+pub(crate) async fn get_many<R>(meili: &MeiliMelo<'_>, index: &str, uids: &[String]) -> Result<Vec<R>, Error>
+where
+  for<'de> R: Deserialize<'de>,
+{
+  if uids.is_empty() {
+    return Ok(Vec::new());
+  }
+
+  let primary_key = primary_key(meili, index).await?;
+  let values: Vec<String> = uids.iter().map(|uid| escape_filter_value(uid)).collect();
+  let filter = format!("{} IN [{}]", primary_key, values.join(", "));
+
+  let query = DocumentsFetch {
+    limit: Some(uids.len() as i64),
+    filter: Some(&filter),
+    ..Default::default()
+  };
+
+  Ok(fetch::<R>(meili, index, &query).await?.results)
 }
 
 pub(crate) async fn get<R>(meili: &MeiliMelo<'_>, index: &str, uid: &str) -> Result<R, Error>
@@ -51,26 +400,199 @@ where
   for<'de> R: Deserialize<'de>,
 {
   let response = meili
-    .request(Method::GET, &format!("/indexes/{}/documents/{}", index, uid))
+    .request(Method::GET, &document_path(index, uid))
     .send()
     .await
-    .map_err(|err| Error::UpstreamError(err))?
-    .json::<R>()
+    .map_err(|err| Error::UpstreamError(err))?;
+
+  meili.json::<R>(response).await
+}
+
+pub(crate) async fn get_opt<R>(meili: &MeiliMelo<'_>, index: &str, uid: &str) -> Result<Option<R>, Error>
+where
+  for<'de> R: Deserialize<'de>,
+{
+  let response = meili
+    .request(Method::GET, &document_path(index, uid))
+    .send()
     .await
     .map_err(|err| Error::UpstreamError(err))?;
 
-  Ok(response)
+  match response.status() {
+    StatusCode::OK => Ok(Some(meili.json::<R>(response).await?)),
+    StatusCode::NOT_FOUND => Ok(None),
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(Error::InvalidQuery(error))
+    }
+  }
+}
+
+pub(crate) async fn get_if_modified<R>(
+  meili: &MeiliMelo<'_>,
+  index: &str,
+  uid: &str,
+  etag: Option<&str>,
+) -> Result<Option<TaggedDocument<R>>, Error>
+where
+  for<'de> R: Deserialize<'de>,
+{
+  let request = meili.request(Method::GET, &document_path(index, uid));
+
+  let request = match etag {
+    Some(etag) => request.header(IF_NONE_MATCH, etag),
+    None => request,
+  };
+
+  let response = request.send().await.map_err(|err| Error::UpstreamError(err))?;
+
+  match response.status() {
+    StatusCode::NOT_MODIFIED => Ok(None),
+
+    StatusCode::OK => {
+      let etag = response
+        .headers()
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+
+      let document = meili.json::<R>(response).await?;
+
+      Ok(Some(TaggedDocument { document, etag }))
+    }
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(Error::InvalidQuery(error))
+    }
+  }
 }
 
 pub(crate) async fn delete(meili: &MeiliMelo<'_>, index: &str, uid: &str) -> Result<Update, Error> {
   let response = meili
-    .request(Method::GET, &format!("/indexes/{}/documents/{}", index, uid))
+    .request(Method::DELETE, &document_path(index, uid))
     .send()
     .await
-    .map_err(|err| Error::UpstreamError(err))?
-    .json::<Update>()
+    .map_err(|err| Error::UpstreamError(err))?;
+
+  match response.status() {
+    status if status.is_success() => Ok(
+      meili
+        .json::<Enqueued>(response)
+        .await?
+        .into_update(index, UpdateKind::DocumentsDeletion),
+    ),
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(Error::InvalidQuery(error))
+    }
+  }
+}
+
+#[cfg(feature = "experimental")]
+#[derive(Debug, Serialize)]
+struct DocumentsEdit<'a> {
+  filter: Option<&'a str>,
+  function: &'a str,
+  context: Option<&'a Value>,
+}
+
+#[cfg(feature = "experimental")]
+pub(crate) async fn edit(
+  meili: &MeiliMelo<'_>,
+  index: &str,
+  filter: Option<&str>,
+  function: &str,
+  context: Option<&Value>,
+) -> Result<Update, Error> {
+  let body = DocumentsEdit {
+    filter,
+    function,
+    context,
+  };
+
+  let response = meili
+    .request(Method::POST, &format!("/indexes/{}/documents/edit", index))
+    .json_body(meili, &body)
+    .send()
     .await
     .map_err(|err| Error::UpstreamError(err))?;
 
-  Ok(response)
+  match response.status() {
+    status if status.is_success() => Ok(
+      meili
+        .json::<Enqueued>(response)
+        .await?
+        .into_update(index, UpdateKind::DocumentsEdit),
+    ),
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(Error::InvalidQuery(error))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use super::{document_path, next_page, PartialUpdate};
+  use crate::{prelude::*, Error};
+
+  #[test]
+  fn partial_update() {
+    let document = PartialUpdate::new(42).set("status", "archived").into_document("id");
+
+    assert_eq!(document, serde_json::json!({ "id": 42, "status": "archived" }));
+  }
+
+  #[test]
+  fn document_path_is_encoded() {
+    assert_eq!(document_path("employees", "42"), "/indexes/employees/documents/42");
+    assert_eq!(
+      document_path("employees", "a/b c?d#e"),
+      "/indexes/employees/documents/a%2Fb%20c%3Fd%23e"
+    );
+  }
+
+  #[tokio::test]
+  async fn insert_stream_errors() {
+    let meili = MeiliMelo::new("http://localhost:7700");
+
+    let documents = futures::stream::iter(vec![serde_json::json!({ "id": 1 })]);
+    let result = meili.insert_stream("employees", documents, 0).await;
+
+    assert!(matches!(result, Err(Error::ValidationError(_))));
+
+    // Maps with non-string keys cannot be serialized to JSON
+    let document: HashMap<(i32, i32), i32> = vec![((1, 2), 3)].into_iter().collect();
+    let documents = futures::stream::iter(vec![document]);
+    let result = meili.insert_stream("employees", documents, 1).await;
+
+    assert!(matches!(result, Err(Error::SerializeError(_))));
+  }
+
+  #[test]
+  fn pages() {
+    assert_eq!(next_page(0, 0, &[]), None);
+
+    assert_eq!(next_page(1000, 0, &[]), Some((1000, 0)));
+    assert_eq!(next_page(1000, 0, &[1000]), None);
+
+    assert_eq!(next_page(2500, 10, &[]), Some((1000, 10)));
+    assert_eq!(next_page(2500, 10, &[1000]), Some((1000, 1010)));
+    assert_eq!(next_page(2500, 10, &[1000, 1000]), Some((500, 2010)));
+    assert_eq!(next_page(2500, 10, &[1000, 1000, 500]), None);
+
+    // A short page means the end of the index was reached
+    assert_eq!(next_page(2500, 0, &[1000, 400]), None);
+    assert_eq!(next_page(20, 0, &[5]), None);
+  }
 }
@@ -1,7 +1,15 @@
+use crate::filters::escape_filter_value;
+
 /// Utility to help build facet filters using the builder pattern
 ///
 /// Calling `build()` will produce a `Facets` struct that can be fed to `Query`'s [`facets()`](struct.Query.html#method.facets).
 ///
+/// Values containing a colon, whitespace, a quote or a backslash are quoted
+/// and escaped so that they do not break the facet filter.
+///
+/// Facet filters are deprecated since MeiliSearch v0.21, in favor of the
+/// expressions produced by [`FilterBuilder`](struct.FilterBuilder.html).
+///
 /// # Examples
 /// ```
 /// # use meilimelo::prelude::*;
@@ -23,19 +31,19 @@ pub struct Facets {
 impl FacetBuilder {
   pub fn new(key: &str, value: &str) -> FacetBuilder {
     FacetBuilder {
-      current: vec![format!("{}:{}", key, value)],
+      current: vec![facet(key, value)],
       accumulator: vec![],
     }
   }
 
   pub fn or(mut self, key: &str, value: &str) -> FacetBuilder {
-    self.current.push(format!("{}:{}", key, value));
+    self.current.push(facet(key, value));
     self
   }
 
   pub fn and(mut self, key: &str, value: &str) -> FacetBuilder {
     self.accumulator.push(self.current);
-    self.current = vec![format!("{}:{}", key, value)];
+    self.current = vec![facet(key, value)];
     self
   }
 
@@ -43,13 +51,87 @@ impl FacetBuilder {
     self.accumulator.push(self.current);
 
     Facets {
-      accumulator: self.accumulator.drain(..).collect(),
+      accumulator: self.accumulator,
     }
   }
 }
 
+fn facet(key: &str, value: &str) -> String {
+  let quote = value
+    .chars()
+    .any(|c| c == ':' || c == '"' || c == '\\' || c.is_whitespace());
+
+  if quote {
+    format!("{}:{}", key, escape_filter_value(value))
+  } else {
+    format!("{}:{}", key, value)
+  }
+}
+
 impl Facets {
   pub(crate) fn get(self) -> Vec<Vec<String>> {
     self.accumulator
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::FacetBuilder;
+
+  fn strings(groups: &[&[&str]]) -> Vec<Vec<String>> {
+    groups
+      .iter()
+      .map(|group| group.iter().map(|facet| facet.to_string()).collect())
+      .collect()
+  }
+
+  #[test]
+  fn single_value() {
+    let facets = FacetBuilder::new("company", "ACME").build().get();
+
+    assert_eq!(facets, strings(&[&["company:ACME"]]));
+  }
+
+  #[test]
+  fn quoted_values() {
+    let facets = FacetBuilder::new("company", "ACME: Corp")
+      .or("company", r#"Big "Corp""#)
+      .build()
+      .get();
+
+    assert_eq!(
+      facets,
+      strings(&[&[r#"company:"ACME: Corp""#, r#"company:"Big \"Corp\"""#]])
+    );
+  }
+
+  #[test]
+  fn single_group() {
+    let facets = FacetBuilder::new("company", "ACME")
+      .or("company", "BigCorp")
+      .build()
+      .get();
+
+    assert_eq!(facets, strings(&[&["company:ACME", "company:BigCorp"]]));
+  }
+
+  #[test]
+  fn groups_order() {
+    let facets = FacetBuilder::new("company", "ACME")
+      .or("company", "BigCorp")
+      .and("roles", "Tech")
+      .and("planet", "Tatooine")
+      .or("planet", "Naboo")
+      .build()
+      .get();
+
+    assert_eq!(
+      facets,
+      strings(&[
+        &["company:ACME", "company:BigCorp"],
+        &["roles:Tech"],
+        &["planet:Tatooine", "planet:Naboo"],
+      ])
+    );
+  }
+}
@@ -0,0 +1,142 @@
+/// Quotes and escapes a value to be safely used in a filter expression
+///
+/// The value is wrapped in double quotes, and any double quote or backslash
+/// it contains is escaped, so that user input cannot break out of the value.
+///
+/// # Arguments
+///
+/// * `value` - raw value to be used in the filter
+///
+/// # Examples
+///
+/// ```
+/// # use meilimelo::prelude::*;
+/// #
+/// let company = meilimelo::escape_filter_value(r#"ACME "Corp""#);
+/// let filter = format!("company = {}", company);
+///
+/// assert_eq!(filter, r#"company = "ACME \"Corp\"""#);
+///
+/// MeiliMelo::new("host").search("index").filters(&filter);
+/// ```
+pub fn escape_filter_value(value: &str) -> String {
+  format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Utility to help build filter expressions using the builder pattern
+///
+/// Unlike [`FacetBuilder`](struct.FacetBuilder.html), which produces the
+/// deprecated `facetFilters` parameter, this builds an expression for the
+/// `filter` parameter supported since MeiliSearch v0.21. Calling `build()`
+/// will produce a string that can be fed to `Query`'s
+/// [`filters()`](struct.Query.html#method.filters). Values are always quoted
+/// and escaped.
+///
+/// # Examples
+///
+/// ```
+/// # use meilimelo::prelude::*;
+/// #
+/// let filter = FilterBuilder::new("company", "ACME Corp")
+///   .or("company", "Big Corp")
+///   .and("roles", "Tech")
+///   .build();
+///
+/// assert_eq!(filter, r#"(company = "ACME Corp" OR company = "Big Corp") AND roles = "Tech""#);
+///
+/// MeiliMelo::new("host").search("index").filters(filter);
+/// ```
+pub struct FilterBuilder {
+  current: Vec<String>,
+  accumulator: Vec<Vec<String>>,
+}
+
+impl FilterBuilder {
+  pub fn new(key: &str, value: &str) -> FilterBuilder {
+    FilterBuilder {
+      current: vec![condition(key, value)],
+      accumulator: vec![],
+    }
+  }
+
+  pub fn or(mut self, key: &str, value: &str) -> FilterBuilder {
+    self.current.push(condition(key, value));
+    self
+  }
+
+  pub fn and(mut self, key: &str, value: &str) -> FilterBuilder {
+    self.accumulator.push(self.current);
+    self.current = vec![condition(key, value)];
+    self
+  }
+
+  pub fn build(mut self) -> String {
+    self.accumulator.push(self.current);
+
+    let single = self.accumulator.len() == 1;
+
+    self
+      .accumulator
+      .into_iter()
+      .map(|group| match group.len() {
+        1 => group.join(""),
+        _ if single => group.join(" OR "),
+        _ => format!("({})", group.join(" OR ")),
+      })
+      .collect::<Vec<_>>()
+      .join(" AND ")
+  }
+}
+
+fn condition(key: &str, value: &str) -> String {
+  format!("{} = {}", key, escape_filter_value(value))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{escape_filter_value, FilterBuilder};
+
+  #[test]
+  fn escape_plain() {
+    assert_eq!(escape_filter_value("ACME Corp"), r#""ACME Corp""#);
+  }
+
+  #[test]
+  fn escape_quotes() {
+    assert_eq!(
+      escape_filter_value(r#"ACME" OR company = "Corp"#),
+      r#""ACME\" OR company = \"Corp""#
+    );
+  }
+
+  #[test]
+  fn escape_backslashes() {
+    assert_eq!(escape_filter_value(r#"ACME\"#), r#""ACME\\""#);
+  }
+
+  #[test]
+  fn filter_single_value() {
+    assert_eq!(FilterBuilder::new("company", "ACME").build(), r#"company = "ACME""#);
+  }
+
+  #[test]
+  fn filter_single_group() {
+    let filter = FilterBuilder::new("company", "ACME").or("company", "BigCorp").build();
+
+    assert_eq!(filter, r#"company = "ACME" OR company = "BigCorp""#);
+  }
+
+  #[test]
+  fn filter_groups() {
+    let filter = FilterBuilder::new("company", "ACME")
+      .and("roles", "Tech")
+      .and("planet", "Tatooine")
+      .or("planet", r#"Naboo "Theed""#)
+      .build();
+
+    assert_eq!(
+      filter,
+      r#"company = "ACME" AND roles = "Tech" AND (planet = "Tatooine" OR planet = "Naboo \"Theed\"")"#
+    );
+  }
+}
@@ -0,0 +1,62 @@
+use std::ops::Deref;
+
+/// Value of an attribute of a hit, either formatted by MeiliSearch or raw
+///
+/// MeiliSearch may omit attributes from `_formatted`, so the formatted value
+/// of an attribute, when highlighted or cropped, is not always available.
+/// This falls back to the raw value of the attribute when it is not, and
+/// can be dereferenced to the value in both cases. The `schema` macro
+/// generates a `formatted_<attribute>()` accessor returning this for every
+/// attribute of the schema.
+///
+/// # Examples
+///
+/// ```
+/// #[meilimelo::schema]
+/// struct Employee {
+///   bio: String,
+/// }
+///
+/// let employee = Employee { bio: "Jedi knight".to_string(), ..Default::default() };
+/// let bio = employee.formatted_bio();
+///
+/// assert!(!bio.is_formatted());
+/// assert_eq!(bio.as_str(), "Jedi knight");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Formatted<'a, T> {
+  /// Value from the `_formatted` object of the hit, possibly highlighted or cropped
+  Formatted(&'a T),
+  /// Raw value of the attribute, used when no formatted value is available
+  Raw(&'a T),
+}
+
+impl<'a, T> Formatted<'a, T> {
+  /// Picks the formatted value if there is one, or the raw value otherwise
+  pub fn new(formatted: Option<&'a T>, raw: &'a T) -> Formatted<'a, T> {
+    match formatted {
+      Some(value) => Formatted::Formatted(value),
+      None => Formatted::Raw(raw),
+    }
+  }
+
+  /// Whether the value comes from the `_formatted` object of the hit
+  pub fn is_formatted(&self) -> bool {
+    matches!(self, Formatted::Formatted(_))
+  }
+
+  /// The formatted value if there is one, or the raw value otherwise
+  pub fn value(&self) -> &'a T {
+    match *self {
+      Formatted::Formatted(value) | Formatted::Raw(value) => value,
+    }
+  }
+}
+
+impl<'a, T> Deref for Formatted<'a, T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    self.value()
+  }
+}
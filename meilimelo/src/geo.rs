@@ -0,0 +1,44 @@
+/// Geographic coordinates of a document, in MeiliSearch's `_geo` format
+///
+/// Geosearch only considers documents whose `_geo` attribute has exactly
+/// this shape, and `_geo` must be added to the filterable or sortable
+/// attributes of the index. It can be embedded in a schema as follows:
+///
+/// ```
+/// use meilimelo::GeoPoint;
+///
+/// #[meilimelo::schema]
+/// struct Restaurant {
+///   name: String,
+///   #[serde(rename = "_geo")]
+///   location: GeoPoint,
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GeoPoint {
+  /// Latitude, in degrees
+  pub lat: f64,
+  /// Longitude, in degrees
+  pub lng: f64,
+}
+
+impl GeoPoint {
+  pub fn new(lat: f64, lng: f64) -> GeoPoint {
+    GeoPoint { lat, lng }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::GeoPoint;
+
+  #[test]
+  fn serialize() {
+    let point = GeoPoint::new(48.8566, 2.3522);
+
+    assert_eq!(
+      serde_json::to_value(point).unwrap(),
+      serde_json::json!({ "lat": 48.8566, "lng": 2.3522 })
+    );
+  }
+}
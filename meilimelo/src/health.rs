@@ -0,0 +1,128 @@
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Deserializer};
+
+use crate::{prelude::*, search::QueryError, Error};
+
+/// Health of a MeiliSearch instance
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Health {
+  pub status: HealthStatus,
+}
+
+/// Status reported by the health endpoint of a MeiliSearch instance
+#[derive(Debug, Clone, PartialEq)]
+pub enum HealthStatus {
+  /// The instance is available to process requests
+  Available,
+  /// The instance reported another status, which is kept as is
+  Unavailable(String),
+}
+
+impl<'de> Deserialize<'de> for HealthStatus {
+  fn deserialize<D>(deserializer: D) -> Result<HealthStatus, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let status = String::deserialize(deserializer)?;
+
+    Ok(match status.as_str() {
+      "available" => HealthStatus::Available,
+      _ => HealthStatus::Unavailable(status),
+    })
+  }
+}
+
+impl Health {
+  /// Whether the instance is available
+  pub fn is_available(&self) -> bool {
+    self.status == HealthStatus::Available
+  }
+}
+
+/// Gets the health of the instance
+///
+/// When `strict` is set, an unexpected HTTP status is returned as an error
+/// instead of being reported as an unavailable status.
+pub(crate) async fn get(meili: &MeiliMelo<'_>, strict: bool) -> Result<Health, Error> {
+  let response = meili
+    .request(Method::GET, "/health")
+    .send()
+    .await
+    .map_err(|err| Error::UpstreamError(err))?;
+
+  match response.status() {
+    // Older versions of MeiliSearch answer with an empty body
+    StatusCode::NO_CONTENT => Ok(Health {
+      status: HealthStatus::Available,
+    }),
+
+    StatusCode::OK => meili.json::<Health>(response).await,
+
+    // Proxies in front of the instance do not answer with MeiliSearch errors
+    status if strict => match meili.json::<QueryError>(response).await {
+      Ok(error) => Err(Error::InvalidQuery(error)),
+      Err(_) => Err(Error::UnexpectedStatus(status)),
+    },
+
+    status => Ok(Health {
+      status: HealthStatus::Unavailable(status.to_string()),
+    }),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    thread,
+  };
+
+  use reqwest::StatusCode;
+
+  use super::{Health, HealthStatus};
+  use crate::{prelude::*, Error};
+
+  #[test]
+  fn health_status() {
+    let health: Health = serde_json::from_str(r#"{ "status": "available" }"#).unwrap();
+    assert_eq!(health.status, HealthStatus::Available);
+    assert!(health.is_available());
+
+    let health: Health = serde_json::from_str(r#"{ "status": "degraded" }"#).unwrap();
+    assert_eq!(health.status, HealthStatus::Unavailable("degraded".to_string()));
+    assert!(!health.is_available());
+  }
+
+  #[cfg(not(target_arch = "wasm32"))]
+  #[tokio::test]
+  async fn unexpected_status() {
+    // Answers like a proxy in front of an unavailable instance
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let host = format!("http://{}", listener.local_addr().unwrap());
+
+    thread::spawn(move || {
+      for stream in listener.incoming().take(2) {
+        let mut stream = stream.unwrap();
+        let mut request = [0; 1024];
+
+        let _ = stream.read(&mut request);
+        let _ = stream.write_all(
+          b"HTTP/1.1 503 Service Unavailable\r\ncontent-type: text/html\r\ncontent-length: 11\r\nconnection: close\r\n\r\nunavailable",
+        );
+      }
+    });
+
+    let meili = MeiliMelo::new(&host);
+
+    assert!(matches!(
+      meili.health().await,
+      Err(Error::UnexpectedStatus(StatusCode::SERVICE_UNAVAILABLE))
+    ));
+
+    assert_eq!(
+      meili.health_detailed().await.unwrap().status,
+      HealthStatus::Unavailable("503 Service Unavailable".to_string())
+    );
+  }
+}
@@ -1,6 +1,12 @@
-use reqwest::Method;
+use reqwest::{Method, StatusCode};
 
-use crate::{prelude::*, Error};
+use crate::{
+  client::JsonBody,
+  prelude::*,
+  search::QueryError,
+  stats::{self, IndexStats},
+  Error,
+};
 
 /// MeiliSearch index descriptor
 #[derive(Debug, Deserialize)]
@@ -15,17 +21,56 @@ pub struct Index {
   pub updated_at: Option<String>,
 }
 
+impl Index {
+  /// Get statistics about the index
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let meili = MeiliMelo::new("host");
+  ///
+  /// for index in meili.indices().await.unwrap() {
+  ///   let stats = index.stats(&meili).await.unwrap();
+  ///
+  ///   println!("{}: {} documents", index.uid, stats.number_of_documents);
+  /// }
+  /// # }
+  /// ```
+  pub async fn stats(&self, meili: &MeiliMelo<'_>) -> Result<IndexStats, Error> {
+    stats::index(meili, &self.uid).await
+  }
+}
+
 pub(crate) async fn list(meili: &MeiliMelo<'_>) -> Result<Vec<Index>, Error> {
   let response = meili
     .request(Method::GET, "/indexes")
     .send()
     .await
-    .map_err(|err| Error::UpstreamError(err))?
-    .json::<Vec<Index>>()
+    .map_err(|err| Error::UpstreamError(err))?;
+
+  meili.json::<Vec<Index>>(response).await
+}
+
+pub(crate) async fn get(meili: &MeiliMelo<'_>, uid: &str) -> Result<Index, Error> {
+  let response = meili
+    .request(Method::GET, &format!("/indexes/{}", uid))
+    .send()
     .await
     .map_err(|err| Error::UpstreamError(err))?;
 
-  Ok(response)
+  match response.status() {
+    StatusCode::OK => meili.json::<Index>(response).await,
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(Error::InvalidQuery(error))
+    }
+  }
 }
 
 #[derive(Debug, Serialize)]
@@ -39,15 +84,75 @@ pub(crate) async fn create(meili: &MeiliMelo<'_>, uid: &str, name: &str) -> Resu
 
   let response = meili
     .request(Method::POST, "/indexes")
-    .json(&body)
+    .json_body(meili, &body)
     .send()
     .await
-    .map_err(|err| Error::UpstreamError(err))?
-    .json::<Index>()
+    .map_err(|err| Error::UpstreamError(err))?;
+
+  match response.status() {
+    status if status.is_success() => meili.json::<Index>(response).await,
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(Error::InvalidQuery(error))
+    }
+  }
+}
+
+pub(crate) async fn ensure(meili: &MeiliMelo<'_>, indices: &[(&str, &str)]) -> Result<Vec<Index>, Error> {
+  let existing = list(meili).await?;
+  let mut created = Vec::new();
+
+  for (uid, name) in indices {
+    if existing.iter().any(|index| index.uid == *uid) {
+      continue;
+    }
+
+    match create(meili, uid, name).await {
+      Ok(index) => created.push(index),
+      Err(Error::InvalidQuery(error)) if error.code == "index_already_exists" => {}
+      Err(err) => return Err(err),
+    }
+  }
+
+  Ok(created)
+}
+
+pub(crate) async fn get_or_create(meili: &MeiliMelo<'_>, uid: &str, name: &str) -> Result<Index, Error> {
+  match get(meili, uid).await {
+    Err(Error::InvalidQuery(error)) if error.code == "index_not_found" => match create(meili, uid, name).await {
+      Err(Error::InvalidQuery(error)) if error.code == "index_already_exists" => get(meili, uid).await,
+      result => result,
+    },
+    result => result,
+  }
+}
+
+#[derive(Debug, Serialize)]
+struct IndexUpdate<'a> {
+  name: &'a str,
+}
+
+pub(crate) async fn rename(meili: &MeiliMelo<'_>, uid: &str, name: &str) -> Result<Index, Error> {
+  let body = IndexUpdate { name };
+
+  let response = meili
+    .request(Method::PUT, &format!("/indexes/{}", uid))
+    .json_body(meili, &body)
+    .send()
     .await
     .map_err(|err| Error::UpstreamError(err))?;
 
-  Ok(response)
+  match response.status() {
+    StatusCode::OK => meili.json::<Index>(response).await,
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(Error::InvalidQuery(error))
+    }
+  }
 }
 
 pub(crate) async fn delete(meili: &MeiliMelo<'_>, uid: &str) -> Result<(), Error> {
@@ -0,0 +1,196 @@
+use reqwest::{Method, StatusCode};
+
+use crate::{client::JsonBody, prelude::*, search::QueryError, Error};
+
+/// Action that can be granted to an API key
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+  All,
+  Search,
+  DocumentsAdd,
+  DocumentsGet,
+  DocumentsDelete,
+  IndexesCreate,
+  IndexesGet,
+  IndexesUpdate,
+  IndexesDelete,
+  TasksGet,
+  SettingsGet,
+  SettingsUpdate,
+  StatsGet,
+  DumpsCreate,
+  Version,
+}
+
+impl Action {
+  fn as_str(&self) -> &'static str {
+    match self {
+      Action::All => "*",
+      Action::Search => "search",
+      Action::DocumentsAdd => "documents.add",
+      Action::DocumentsGet => "documents.get",
+      Action::DocumentsDelete => "documents.delete",
+      Action::IndexesCreate => "indexes.create",
+      Action::IndexesGet => "indexes.get",
+      Action::IndexesUpdate => "indexes.update",
+      Action::IndexesDelete => "indexes.delete",
+      Action::TasksGet => "tasks.get",
+      Action::SettingsGet => "settings.get",
+      Action::SettingsUpdate => "settings.update",
+      Action::StatsGet => "stats.get",
+      Action::DumpsCreate => "dumps.create",
+      Action::Version => "version",
+    }
+  }
+}
+
+/// Body of an API key creation request
+///
+/// [`KeyBuilder`](struct.KeyBuilder.html) should be used to create it.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct CreateKey {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub description: Option<String>,
+  pub actions: Vec<String>,
+  pub indexes: Vec<String>,
+  #[serde(rename = "expiresAt")]
+  pub expires_at: Option<String>,
+}
+
+/// MeiliSearch API key descriptor
+#[derive(Debug, Deserialize)]
+pub struct Key {
+  pub key: String,
+  pub description: Option<String>,
+  pub actions: Vec<String>,
+  pub indexes: Vec<String>,
+  #[serde(rename = "expiresAt")]
+  pub expires_at: Option<String>,
+  #[serde(rename = "createdAt")]
+  pub created_at: Option<String>,
+  #[serde(rename = "updatedAt")]
+  pub updated_at: Option<String>,
+}
+
+/// Utility to help build API key creation requests using the builder pattern
+///
+/// Calling `build()` will produce a `CreateKey` struct that can be fed to
+/// [`MeiliMelo::create_key()`](../struct.MeiliMelo.html#method.create_key).
+/// If no index is given, the key is valid on all indices.
+///
+/// # Examples
+///
+/// ```
+/// # use meilimelo::KeyBuilder;
+/// #
+/// KeyBuilder::new()
+///   .description("Frontend search")
+///   .allow_search()
+///   .on_indexes(&["products"])
+///   .expires_at("2030-01-01T00:00:00Z")
+///   .build();
+/// ```
+#[derive(Debug, Default)]
+pub struct KeyBuilder {
+  key: CreateKey,
+}
+
+impl KeyBuilder {
+  pub fn new() -> KeyBuilder {
+    KeyBuilder::default()
+  }
+
+  pub fn description(mut self, description: &str) -> KeyBuilder {
+    self.key.description = Some(description.to_string());
+    self
+  }
+
+  pub fn allow(mut self, action: Action) -> KeyBuilder {
+    self.key.actions.push(action.as_str().to_string());
+    self
+  }
+
+  pub fn allow_all(self) -> KeyBuilder {
+    self.allow(Action::All)
+  }
+
+  pub fn allow_search(self) -> KeyBuilder {
+    self.allow(Action::Search)
+  }
+
+  pub fn allow_documents_add(self) -> KeyBuilder {
+    self.allow(Action::DocumentsAdd)
+  }
+
+  pub fn allow_documents_get(self) -> KeyBuilder {
+    self.allow(Action::DocumentsGet)
+  }
+
+  pub fn allow_documents_delete(self) -> KeyBuilder {
+    self.allow(Action::DocumentsDelete)
+  }
+
+  pub fn on_indexes(mut self, indexes: &[&str]) -> KeyBuilder {
+    self.key.indexes.extend(indexes.iter().map(|index| index.to_string()));
+    self
+  }
+
+  /// Sets the expiration date of the key, as an RFC 3339 datetime
+  pub fn expires_at(mut self, datetime: &str) -> KeyBuilder {
+    self.key.expires_at = Some(datetime.to_string());
+    self
+  }
+
+  pub fn build(mut self) -> CreateKey {
+    if self.key.indexes.is_empty() {
+      self.key.indexes.push("*".to_string());
+    }
+
+    self.key
+  }
+}
+
+pub(crate) async fn create(meili: &MeiliMelo<'_>, key: &CreateKey) -> Result<Key, Error> {
+  let response = meili
+    .request(Method::POST, "/keys")
+    .json_body(meili, key)
+    .send()
+    .await
+    .map_err(|err| Error::UpstreamError(err))?;
+
+  match response.status() {
+    StatusCode::OK | StatusCode::CREATED => meili.json::<Key>(response).await,
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(Error::InvalidQuery(error))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{Action, KeyBuilder};
+
+  #[test]
+  fn build() {
+    let key = KeyBuilder::new()
+      .allow_search()
+      .allow(Action::DocumentsAdd)
+      .on_indexes(&["products"])
+      .build();
+
+    assert_eq!(key.actions, vec!["search".to_string(), "documents.add".to_string()]);
+    assert_eq!(key.indexes, vec!["products".to_string()]);
+    assert_eq!(key.expires_at, None);
+  }
+
+  #[test]
+  fn build_all_indexes() {
+    let key = KeyBuilder::new().allow_all().build();
+
+    assert_eq!(key.actions, vec!["*".to_string()]);
+    assert_eq!(key.indexes, vec!["*".to_string()]);
+  }
+}
@@ -1,214 +1,1863 @@
 #[macro_use]
 extern crate serde;
 
+#[cfg(feature = "blocking")]
+mod blocking;
+mod client;
 mod documents;
 mod facets;
+mod filters;
+mod formatted;
+mod geo;
+mod health;
 mod indices;
+mod keys;
+mod multi;
 mod results;
+#[cfg(all(
+  not(target_arch = "wasm32"),
+  any(feature = "tokio-runtime", feature = "async-std-runtime")
+))]
+mod runtime;
 mod search;
+mod settings;
+#[cfg(feature = "experimental")]
+mod similar;
+mod stats;
+mod updates;
+mod version;
 
 /// Most user-facing facilities can be imported through this
 pub mod prelude {
   pub use crate::{
     facets::FacetBuilder,
+    filters::FilterBuilder,
     results::Results,
-    search::{Crop, Query},
+    search::{Crop, Query, QueryText, SearchParams, Sort},
     MeiliMelo,
   };
 }
 
-use reqwest::{Client, Method, RequestBuilder};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+use std::{borrow::Cow, collections::HashMap, fmt::Display};
+
+use futures::Stream;
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use self::search::QueryError;
+use self::{client::ClientConfig, search::QueryError};
 
+#[cfg(feature = "blocking")]
+pub use self::blocking::DocumentsIter;
 pub use self::{
-  documents::Update,
+  documents::{DocumentsFetch, DocumentsPage, InsertMode, PartialUpdate, TaggedDocument},
   facets::FacetBuilder,
+  filters::{escape_filter_value, FilterBuilder},
+  formatted::Formatted,
+  geo::GeoPoint,
+  health::{Health, HealthStatus},
   indices::Index,
-  search::{Crop, Query},
+  keys::{Action, CreateKey, Key, KeyBuilder},
+  multi::{MultiResults, MultiSearch},
+  results::{RankingRuleScore, RankingScoreDetails},
+  search::{Crop, MatchingStrategy, Query, QueryError, QueryText, SearchParams, Sort},
+  settings::Settings,
+  stats::{IndexStats, Stats},
+  updates::{Status, Update, UpdateDetails, UpdateKind, UpdateStatus, UpdateType},
+  version::Version,
 };
 pub use meilimelo_macros::schema;
 
+#[doc(hidden)]
+pub mod __private {
+  use serde::{Deserialize, Deserializer};
+  use serde_json::Value;
+
+  /// Deserializes a formatted attribute, falling back to `None` if it does not match the original type
+  pub fn lenient<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+  where
+    D: Deserializer<'de>,
+    T: for<'a> Deserialize<'a>,
+  {
+    let value = Value::deserialize(deserializer)?;
+
+    Ok(T::deserialize(value).ok())
+  }
+}
+
+/// Address of a MeiliSearch instance running locally on the default port
+const LOCAL_HOST: &str = "http://localhost:7700";
+
 /// Pseudo-marker trait for MeiliSearch schemas
 pub trait Schema: Default + Serialize + for<'de> Deserialize<'de> {}
 
 /// Descriptor to a MeiliSearch instance
-#[derive(Debug, Default)]
+///
+/// Cloning a descriptor is cheap, and the clone shares the connection pool of
+/// the original one.
+#[derive(Debug, Default, Clone)]
 pub struct MeiliMelo<'m> {
   /// Base hostname and port to the instance, including the scheme
-  host: &'m str,
+  host: Cow<'m, str>,
   /// Secret key to be used with the requests to MeiliSearch
-  secret_key: Option<&'m str>,
+  secret_key: Option<Cow<'m, str>>,
+  /// Value of the `Content-Type` header sent with JSON bodies, instead of `application/json`
+  content_type: Option<&'m str>,
+  /// Value of the `Accept` header sent with all requests
+  accept: Option<&'m str>,
+  /// Settings of the HTTP client
+  config: ClientConfig,
+  /// HTTP client shared by all requests to the instance
+  client: Client,
 }
 
 /// Errors emitted by the library
 #[derive(Debug, Error)]
 pub enum Error {
-  /// Error originating from the communication with the instance, either upstream or when parsing its responses
+  /// Error originating from the communication with the instance
   #[error("upstream error")]
   UpstreamError(#[from] reqwest::Error),
   /// The crafted query was refused by the instance
-  #[error("meilisearch query error")]
-  InvalidQuery(QueryError),
+  #[error("meilisearch query error: {0}")]
+  InvalidQuery(#[from] QueryError),
+  /// The index has no primary key and none could be inferred from the inserted documents
+  ///
+  /// The primary key should be explicitly set on the index before inserting documents.
+  #[error("missing primary key, it could not be inferred from the documents: {0}")]
+  MissingPrimaryKey(#[source] QueryError),
+  /// The response sent by the instance could not be deserialized
+  #[error("could not parse response")]
+  ParseError(#[source] Box<dyn std::error::Error + Send + Sync>),
+  /// The body of the request could not be serialized
+  #[error("could not serialize request")]
+  SerializeError(#[source] Box<dyn std::error::Error + Send + Sync>),
+  /// The request was rejected before being sent, for example because some of its parameters are contradictory
+  #[error("invalid query: {0}")]
+  ValidationError(String),
+  /// The deadline set on the request was reached before it completed
+  #[error("deadline exceeded")]
+  DeadlineExceeded,
+  /// The response sent by the instance exceeded the configured maximum size, in bytes
+  #[error("response body exceeds the maximum of {0} bytes")]
+  ResponseTooLarge(usize),
+  /// The instance answered with an unexpected HTTP status and no MeiliSearch error
+  #[error("unexpected HTTP status: {0}")]
+  UnexpectedStatus(StatusCode),
 }
 
-impl<'m> MeiliMelo<'m> {
-  /// Creates a new descriptor to a MeiliSearch instance
+impl<'m> MeiliMelo<'m> {
+  /// Creates a new descriptor to a MeiliSearch instance
+  ///
+  /// # Arguments
+  ///
+  /// * `host` - Scheme, hostname and port to the MeiliSearch instance, a trailing slash being ignored
+  pub fn new(host: &str) -> MeiliMelo {
+    MeiliMelo {
+      host: Cow::Borrowed(host.strip_suffix('/').unwrap_or(host)),
+      ..Default::default()
+    }
+  }
+
+  /// Creates a new descriptor to a MeiliSearch instance, owning its host
+  ///
+  /// Unlike [`new()`](#method.new), the returned descriptor does not borrow
+  /// its host, which allows to store it when the host is only known at
+  /// runtime, for example from the environment.
+  ///
+  /// # Arguments
+  ///
+  /// * `host` - Scheme, hostname and port to the MeiliSearch instance, a trailing slash being ignored
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// use meilimelo::prelude::*;
+  ///
+  /// struct App {
+  ///   meili: MeiliMelo<'static>,
+  /// }
+  ///
+  /// let host = std::env::var("MEILI_HOST").unwrap();
+  /// let key = std::env::var("MEILI_MASTER_KEY").unwrap();
+  ///
+  /// let app = App {
+  ///   meili: MeiliMelo::new_owned(host).with_secret_key(key),
+  /// };
+  /// ```
+  pub fn new_owned(mut host: String) -> MeiliMelo<'static> {
+    if host.ends_with('/') {
+      host.pop();
+    }
+
+    MeiliMelo {
+      host: Cow::Owned(host),
+      ..Default::default()
+    }
+  }
+
+  /// Creates a new descriptor to a MeiliSearch instance running locally on the default port
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meilimelo::prelude::*;
+  ///
+  /// let m = MeiliMelo::local();
+  /// ```
+  pub fn local() -> MeiliMelo<'m> {
+    MeiliMelo::new(LOCAL_HOST)
+  }
+
+  /// Creates a new descriptor to a MeiliSearch instance running locally on the default port, with a secret key
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - The string representation of the secret key
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// use meilimelo::prelude::*;
+  ///
+  /// let key = std::env::var("MEILI_MASTER_KEY").unwrap();
+  /// let m = MeiliMelo::local_with_key(&key);
+  /// ```
+  pub fn local_with_key(key: &'m str) -> MeiliMelo<'m> {
+    MeiliMelo::local().with_secret_key(key)
+  }
+
+  pub(crate) fn request(&self, method: Method, path: &str) -> RequestBuilder {
+    self.request_with_key(method, path, self.secret_key.as_deref())
+  }
+
+  pub(crate) fn request_with_key(&self, method: Method, path: &str, key: Option<&str>) -> RequestBuilder {
+    let url = format!("{}{}", self.host, path);
+
+    self
+      .headers(key)
+      .into_iter()
+      .fold(self.client.request(method, &url), |request, (name, value)| {
+        request.header(name, value)
+      })
+  }
+
+  /// Headers sent with every request, shared by the async and blocking clients
+  pub(crate) fn headers<'k>(&'k self, key: Option<&'k str>) -> Vec<(&'static str, &'k str)> {
+    let mut headers = Vec::new();
+
+    if let Some(key) = key {
+      headers.push(("X-Meili-API-Key", key));
+    }
+
+    if let Some(accept) = self.accept {
+      headers.push(("Accept", accept));
+    }
+
+    headers
+  }
+
+  #[cfg(not(feature = "simd-json"))]
+  pub(crate) async fn json<R>(&self, response: Response) -> Result<R, Error>
+  where
+    for<'de> R: Deserialize<'de>,
+  {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+      if let Some(max) = self.config.max_response_bytes {
+        let body = client::read_limited(response, max).await?;
+
+        return serde_json::from_slice::<R>(&body).map_err(|err| Error::ParseError(Box::new(err)));
+      }
+    }
+
+    let body = response.bytes().await.map_err(|err| Error::UpstreamError(err))?;
+
+    serde_json::from_slice::<R>(&body).map_err(|err| Error::ParseError(Box::new(err)))
+  }
+
+  #[cfg(feature = "simd-json")]
+  pub(crate) async fn json<R>(&self, response: Response) -> Result<R, Error>
+  where
+    for<'de> R: Deserialize<'de>,
+  {
+    #[cfg(not(target_arch = "wasm32"))]
+    let mut body = match self.config.max_response_bytes {
+      Some(max) => client::read_limited(response, max).await?,
+      None => response
+        .bytes()
+        .await
+        .map_err(|err| Error::UpstreamError(err))?
+        .to_vec(),
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    let mut body = response
+      .bytes()
+      .await
+      .map_err(|err| Error::UpstreamError(err))?
+      .to_vec();
+
+    simd_json::serde::from_slice::<R>(&mut body).map_err(|err| Error::ParseError(Box::new(err)))
+  }
+
+  /// Adds the secret key to be used to authenticate against MeiliSearch
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - The string representation of the secret key, either borrowed or owned
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meilimelo::prelude::*;
+  ///
+  /// let m = MeiliMelo::new("https://meilisearch.example.com:7700")
+  ///   .with_secret_key("abcdef");
+  /// ```
+  pub fn with_secret_key<K>(mut self, key: K) -> MeiliMelo<'m>
+  where
+    K: Into<Cow<'m, str>>,
+  {
+    self.secret_key = Some(key.into());
+    self
+  }
+
+  /// Overrides the `Content-Type` header sent with JSON request bodies
+  ///
+  /// By default, JSON bodies are sent as `application/json`. This can be
+  /// needed by proxies requiring specific content negotiation, and does not
+  /// apply to bodies sent in other formats, such as NDJSON, nor to requests
+  /// without a body.
+  ///
+  /// # Arguments
+  ///
+  /// * `content_type` - value of the `Content-Type` header
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meilimelo::prelude::*;
+  ///
+  /// let m = MeiliMelo::new("https://meilisearch.example.com:7700")
+  ///   .with_content_type("application/json; charset=utf-8");
+  /// ```
+  pub fn with_content_type(mut self, content_type: &'m str) -> MeiliMelo<'m> {
+    self.content_type = Some(content_type);
+    self
+  }
+
+  /// Sets the `Accept` header sent with all requests
+  ///
+  /// By default, no `Accept` header is sent, and MeiliSearch answers with
+  /// JSON. Responses are always parsed as JSON, whatever this header says.
+  ///
+  /// # Arguments
+  ///
+  /// * `accept` - value of the `Accept` header
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meilimelo::prelude::*;
+  ///
+  /// let m = MeiliMelo::new("https://meilisearch.example.com:7700")
+  ///   .with_accept("application/json");
+  /// ```
+  pub fn with_accept(mut self, accept: &'m str) -> MeiliMelo<'m> {
+    self.accept = Some(accept);
+    self
+  }
+
+  /// Returns a descriptor using another secret key
+  ///
+  /// The returned descriptor shares the connection pool of the current one,
+  /// which makes it suitable to perform operations on behalf of several
+  /// tenants without creating a new client for each of them.
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - The string representation of the secret key or tenant token
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meilimelo::prelude::*;
+  ///
+  /// let m = MeiliMelo::new("https://meilisearch.example.com:7700")
+  ///   .with_secret_key("abcdef");
+  ///
+  /// let tenant = m.for_key("tenant-token");
+  /// ```
+  pub fn for_key(&self, key: &'m str) -> MeiliMelo<'m> {
+    MeiliMelo {
+      secret_key: Some(Cow::Borrowed(key)),
+      ..self.clone()
+    }
+  }
+
+  /// Sets the maximum number of idle connections kept open to the instance
+  ///
+  /// By default, there is no limit.
+  ///
+  /// # Arguments
+  ///
+  /// * `max` - maximum number of idle connections
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meilimelo::prelude::*;
+  ///
+  /// let m = MeiliMelo::new("https://meilisearch.example.com:7700")
+  ///   .with_pool_max_idle(16);
+  /// ```
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn with_pool_max_idle(mut self, max: usize) -> MeiliMelo<'m> {
+    self.config.pool_max_idle = Some(max);
+    self.client = self.config.build();
+    self
+  }
+
+  /// Sets the duration after which idle connections to the instance are closed
+  ///
+  /// By default, idle connections are closed after 90 seconds.
+  ///
+  /// # Arguments
+  ///
+  /// * `timeout` - duration after which an idle connection is closed
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meilimelo::prelude::*;
+  /// use std::time::Duration;
+  ///
+  /// let m = MeiliMelo::new("https://meilisearch.example.com:7700")
+  ///   .with_pool_idle_timeout(Duration::from_secs(30));
+  /// ```
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn with_pool_idle_timeout(mut self, timeout: Duration) -> MeiliMelo<'m> {
+    self.config.pool_idle_timeout = Some(timeout);
+    self.client = self.config.build();
+    self
+  }
+
+  /// Sets the maximum duration allowed to establish a connection to the instance
+  ///
+  /// By default, there is no connection timeout.
+  ///
+  /// # Arguments
+  ///
+  /// * `timeout` - maximum duration of the connection phase
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meilimelo::prelude::*;
+  /// use std::time::Duration;
+  ///
+  /// let m = MeiliMelo::new("https://meilisearch.example.com:7700")
+  ///   .with_connect_timeout(Duration::from_secs(1));
+  /// ```
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn with_connect_timeout(mut self, timeout: Duration) -> MeiliMelo<'m> {
+    self.config.connect_timeout = Some(timeout);
+    self.client = self.config.build();
+    self
+  }
+
+  /// Sets the maximum duration allowed to receive a response from the instance
+  ///
+  /// By default, there is no read timeout. The underlying HTTP client does
+  /// not distinguish the phases of a request, so this duration is counted
+  /// from the start of the request until its response is fully read, while
+  /// [`with_connect_timeout`](#method.with_connect_timeout) bounds the
+  /// connection phase only.
+  ///
+  /// # Arguments
+  ///
+  /// * `timeout` - maximum duration to receive a complete response
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meilimelo::prelude::*;
+  /// use std::time::Duration;
+  ///
+  /// let m = MeiliMelo::new("https://meilisearch.example.com:7700")
+  ///   .with_connect_timeout(Duration::from_secs(1))
+  ///   .with_read_timeout(Duration::from_secs(30));
+  /// ```
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn with_read_timeout(mut self, timeout: Duration) -> MeiliMelo<'m> {
+    self.config.read_timeout = Some(timeout);
+    self.client = self.config.build();
+    self
+  }
+
+  /// Sets the maximum duration of every request sent to the instance
+  ///
+  /// By default, there is no timeout. The duration is counted from the start
+  /// of the request until its response is fully read, which makes this
+  /// method equivalent to [`with_read_timeout`](#method.with_read_timeout).
+  /// Requests that time out fail with
+  /// [`Error::UpstreamError`](enum.Error.html#variant.UpstreamError), on
+  /// which `is_timeout()` returns `true`.
+  ///
+  /// # Arguments
+  ///
+  /// * `timeout` - maximum duration of a request
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meilimelo::prelude::*;
+  /// use std::time::Duration;
+  ///
+  /// let m = MeiliMelo::new("https://meilisearch.example.com:7700")
+  ///   .with_secret_key("helloworld")
+  ///   .with_timeout(Duration::from_secs(5));
+  /// ```
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn with_timeout(self, timeout: Duration) -> MeiliMelo<'m> {
+    self.with_read_timeout(timeout)
+  }
+
+  /// Sets the maximum size of the responses read from the instance
+  ///
+  /// By default, responses are fully buffered whatever their size. When set,
+  /// reading a larger response is aborted as soon as the limit is reached,
+  /// and fails with [`Error::ResponseTooLarge`](enum.Error.html#variant.ResponseTooLarge).
+  ///
+  /// # Arguments
+  ///
+  /// * `max` - maximum number of bytes of a response body
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meilimelo::prelude::*;
+  ///
+  /// let m = MeiliMelo::new("https://meilisearch.example.com:7700")
+  ///   .with_max_response_bytes(10 * 1024 * 1024);
+  /// ```
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn with_max_response_bytes(mut self, max: usize) -> MeiliMelo<'m> {
+    self.config.max_response_bytes = Some(max);
+    self
+  }
+
+  /// Measures the duration of searches as observed by the client
+  ///
+  /// When enabled, the duration is available through
+  /// [`Results::client_duration()`](prelude/struct.Results.html#method.client_duration).
+  ///
+  /// # Arguments
+  ///
+  /// * `enabled` - whether to measure the duration of searches
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meilimelo::prelude::*;
+  ///
+  /// let m = MeiliMelo::new("https://meilisearch.example.com:7700")
+  ///   .with_timing(true);
+  /// ```
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn with_timing(mut self, enabled: bool) -> MeiliMelo<'m> {
+    self.config.timing = enabled;
+    self
+  }
+
+  /// Disables the verification of the TLS certificate presented by the instance
+  ///
+  /// This makes the connection vulnerable to man-in-the-middle attacks, and
+  /// is only meant to reach development instances using a self-signed
+  /// certificate. It requires the `insecure-tls` feature, which should never
+  /// be enabled in production builds.
+  ///
+  /// # Arguments
+  ///
+  /// * `accept` - whether to accept invalid certificates
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use meilimelo::prelude::*;
+  ///
+  /// let m = MeiliMelo::new("https://localhost:7700")
+  ///   .danger_accept_invalid_certs(true);
+  /// ```
+  #[cfg(all(feature = "insecure-tls", not(target_arch = "wasm32")))]
+  pub fn danger_accept_invalid_certs(mut self, accept: bool) -> MeiliMelo<'m> {
+    self.config.accept_invalid_certs = accept;
+    self.client = self.config.build();
+    self
+  }
+
+  /// Initialize a search query
+  ///
+  /// The returned struct implements the builder pattern and allows to
+  /// construct the query incrementally. Please see
+  /// [`Query`](search/struct.Query.html) for details on the available methods.
+  ///
+  /// # Arguments
+  ///
+  /// * `index` - The name of the index to search
+  pub fn search(&'m self, index: &'m str) -> Query<'_> {
+    Query::new(self, index)
+  }
+
+  /// Initialize a search query from owned search parameters
+  ///
+  /// The parameters are typically deserialized from the body of an incoming
+  /// request, and should be validated before being applied. The returned
+  /// query can be further customized before being run.
+  ///
+  /// # Arguments
+  ///
+  /// * `index` - name of the index to search into
+  /// * `params` - parameters of the search
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::{prelude::*, SearchParams};
+  /// #
+  /// # #[meilimelo::schema]
+  /// # struct Employee;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let meili = MeiliMelo::new("host");
+  /// let params: SearchParams = serde_json::from_str(r#"{ "q": "skywalker" }"#).unwrap();
+  ///
+  /// let results = meili.search_with("employees", &params).run::<Employee>().await;
+  /// # }
+  /// ```
+  pub fn search_with(&'m self, index: &'m str, params: &'m SearchParams) -> Query<'_> {
+    Query::new(self, index).apply(params)
+  }
+
+  /// Run the same query against all indices matching a predicate
+  ///
+  /// The indices are listed, and the query is run concurrently against each
+  /// one for which `predicate` returns `true`, ignoring the index the query
+  /// was initialized with. This fails as soon as one of the searches fails.
+  ///
+  /// # Arguments
+  ///
+  /// * `query` - query to run against each index
+  /// * `predicate` - function returning whether an index should be searched
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[meilimelo::schema]
+  /// # struct Document;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let meili = MeiliMelo::new("host");
+  /// let query = meili.search("").query("invoice");
+  ///
+  /// let results = meili
+  ///   .search_indices_where::<Document, _>(query, |index| index.name.contains("docs"))
+  ///   .await;
+  /// # }
+  /// ```
+  pub async fn search_indices_where<R, F>(
+    &'m self,
+    query: Query<'m>,
+    predicate: F,
+  ) -> Result<HashMap<String, results::Results<R>>, Error>
+  where
+    R: Schema + for<'de> Deserialize<'de>,
+    F: Fn(&Index) -> bool,
+  {
+    let indices: Vec<Index> = indices::list(self)
+      .await?
+      .into_iter()
+      .filter(|index| predicate(index))
+      .collect();
+
+    let searches = indices.iter().map(|index| {
+      let query = query.clone().on_index(&index.uid);
+
+      async move { query.execute::<R>().await.map(|results| (index.uid.clone(), results)) }
+    });
+
+    Ok(futures::future::try_join_all(searches).await?.into_iter().collect())
+  }
+
+  /// Run the same query against all indices whose unique ID starts with a prefix
+  ///
+  /// This is useful when data is sharded into several indices, for example
+  /// one per tenant. Please see
+  /// [`search_indices_where()`](#method.search_indices_where) for details.
+  ///
+  /// # Arguments
+  ///
+  /// * `prefix` - prefix of the unique IDs of the indices to search
+  /// * `query` - query to run against each index
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[meilimelo::schema]
+  /// # struct Document;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let meili = MeiliMelo::new("host");
+  /// let query = meili.search("").query("invoice");
+  ///
+  /// for (index, results) in meili.search_indices_with_prefix::<Document>("docs_", query).await.unwrap() {
+  ///   println!("{}: {} hits", index, results.hits);
+  /// }
+  /// # }
+  /// ```
+  pub async fn search_indices_with_prefix<R>(
+    &'m self,
+    prefix: &str,
+    query: Query<'m>,
+  ) -> Result<HashMap<String, results::Results<R>>, Error>
+  where
+    R: Schema + for<'de> Deserialize<'de>,
+  {
+    self
+      .search_indices_where(query, |index| index.uid.starts_with(prefix))
+      .await
+  }
+
+  /// Initialize a multi-search request
+  ///
+  /// Several queries, possibly on different indices, can then be added to
+  /// the request and run at once. Please see
+  /// [`MultiSearch`](struct.MultiSearch.html) for details.
+  pub fn multi_search(&'m self) -> MultiSearch<'m> {
+    MultiSearch::new(self)
+  }
+
+  /// Get the health of the instance, failing if it answers with an unexpected HTTP status
+  ///
+  /// Unlike [`health_detailed()`](#method.health_detailed), an instance
+  /// answering with an HTTP status other than `200` or `204` is reported as
+  /// an error, which suits probes that need to tell a failing instance from
+  /// a degraded one. When the response is not a MeiliSearch error, for
+  /// example when it comes from a proxy, the error is
+  /// [`Error::UnexpectedStatus`](enum.Error.html#variant.UnexpectedStatus).
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// match MeiliMelo::new("host").health().await {
+  ///   Ok(health) => println!("{:?}", health.status),
+  ///   Err(err) => println!("health check failed: {}", err),
+  /// }
+  /// # }
+  /// ```
+  pub async fn health(&'m self) -> Result<Health, Error> {
+    health::get(self, true).await
+  }
+
+  /// Get the health of the instance
+  ///
+  /// An instance answering with an unexpected HTTP status is reported as
+  /// unavailable, while errors in reaching it are returned as such.
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::{prelude::*, HealthStatus};
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let meili = MeiliMelo::new("host");
+  ///
+  /// match meili.health_detailed().await.unwrap().status {
+  ///   HealthStatus::Available => println!("available"),
+  ///   HealthStatus::Unavailable(status) => println!("unavailable: {}", status),
+  /// }
+  /// # }
+  /// ```
+  pub async fn health_detailed(&'m self) -> Result<Health, Error> {
+    health::get(self, false).await
+  }
+
+  /// Whether the instance is reachable and available
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// if !MeiliMelo::new("host").is_healthy().await {
+  ///   println!("MeiliSearch is down");
+  /// }
+  /// # }
+  /// ```
+  pub async fn is_healthy(&'m self) -> bool {
+    matches!(self.health_detailed().await, Ok(health) if health.is_available())
+  }
+
+  /// List all available indices
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let meili = MeiliMelo::new("host");
+  ///
+  /// for index in meili.indices().await.unwrap() {
+  ///   println!("{}", index.name);
+  /// }
+  /// # }
+  /// ```
+  pub async fn indices(&'m self) -> Result<Vec<Index>, Error> {
+    indices::list(self).await
+  }
+
+  /// Get an index
+  ///
+  /// # Arguments
+  ///
+  /// * `uid` - unique ID of the index
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let index = MeiliMelo::new("host").get_index("employees").await.unwrap();
+  ///
+  /// println!("{}", index.name);
+  /// # }
+  /// ```
+  pub async fn get_index(&'m self, uid: &str) -> Result<Index, Error> {
+    indices::get(self, uid).await
+  }
+
+  /// Get an index, creating it if it does not exist
+  ///
+  /// # Arguments
+  ///
+  /// * `uid` - unique ID of the index
+  /// * `name` - human-readable name for the index, if it is created
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let index = MeiliMelo::new("host")
+  ///   .get_or_create_index("employees", "Employees")
+  ///   .await
+  ///   .unwrap();
+  /// # }
+  /// ```
+  pub async fn get_or_create_index(&'m self, uid: &str, name: &str) -> Result<Index, Error> {
+    indices::get_or_create(self, uid, name).await
+  }
+
+  /// Get an index, creating it if it does not exist, alongside its current settings
+  ///
+  /// This is useful to compare the actual settings of an index to the
+  /// desired ones before deciding whether to update them.
+  ///
+  /// # Arguments
+  ///
+  /// * `uid` - unique ID of the index
+  /// * `name` - human-readable name for the index, if it is created
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let (index, settings) = MeiliMelo::new("host")
+  ///   .get_or_create_index_with_settings("employees", "Employees")
+  ///   .await
+  ///   .unwrap();
+  ///
+  /// if settings.distinct_attribute.is_none() {
+  ///   println!("{} needs to be configured", index.uid);
+  /// }
+  /// # }
+  /// ```
+  pub async fn get_or_create_index_with_settings(&'m self, uid: &str, name: &str) -> Result<(Index, Settings), Error> {
+    let index = indices::get_or_create(self, uid, name).await?;
+    let settings = settings::get::<Settings>(self, uid).await?;
+
+    Ok((index, settings))
+  }
+
+  /// Create a new index
+  ///
+  /// # Arguments
+  ///
+  /// * `uid` - unique ID for the new index
+  /// * `name` - human-readable name for the index
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// MeiliMelo::new("host")
+  ///   .create_index("employees", "Employees")
+  ///   .await;
+  /// # }
+  /// ```
+  pub async fn create_index<'a>(&'m self, uid: &str, name: &str) -> Result<Index, Error> {
+    indices::create(self, uid, name).await
+  }
+
+  /// Create the given indices if they do not exist yet
+  ///
+  /// This is safe to call concurrently from several processes: an index
+  /// created by someone else in the meantime is not considered an error.
+  /// Only the indices created by this call are returned.
+  ///
+  /// # Arguments
+  ///
+  /// * `indices` - unique IDs and human-readable names of the indices
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let created = MeiliMelo::new("host")
+  ///   .ensure_indices(&[("employees", "Employees"), ("companies", "Companies")])
+  ///   .await
+  ///   .unwrap();
+  ///
+  /// for index in created {
+  ///   println!("created {}", index.uid);
+  /// }
+  /// # }
+  /// ```
+  pub async fn ensure_indices(&'m self, indices: &[(&str, &str)]) -> Result<Vec<Index>, Error> {
+    indices::ensure(self, indices).await
+  }
+
+  /// Create a new index and apply settings to it
+  ///
+  /// This returns as soon as the settings update is enqueued, alongside
+  /// the created index. Use
+  /// [`create_index_configured_and_wait()`](#method.create_index_configured_and_wait)
+  /// to wait for the settings to be applied.
+  ///
+  /// # Arguments
+  ///
+  /// * `uid` - unique ID for the new index
+  /// * `name` - human-readable name for the index
+  /// * `settings` - settings to apply to the new index
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::{prelude::*, Settings};
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let settings = Settings {
+  ///   searchable_attributes: Some(vec!["firstname".to_string(), "lastname".to_string()]),
+  ///   ..Default::default()
+  /// };
+  ///
+  /// MeiliMelo::new("host")
+  ///   .create_index_configured("employees", "Employees", &settings)
+  ///   .await;
+  /// # }
+  /// ```
+  pub async fn create_index_configured(
+    &'m self,
+    uid: &str,
+    name: &str,
+    settings: &Settings,
+  ) -> Result<(Index, Update), Error> {
+    let index = indices::create(self, uid, name).await?;
+    let update = settings::update(self, uid, settings).await?;
+
+    Ok((index, update))
+  }
+
+  /// Create a new index, apply settings to it and wait for them to be applied
+  ///
+  /// Unlike [`create_index_configured()`](#method.create_index_configured),
+  /// this returns once the settings update is finished, alongside
+  /// its final status. This requires either the `tokio-runtime` (enabled by
+  /// default) or the `async-std-runtime` feature.
+  ///
+  /// # Arguments
+  ///
+  /// * `uid` - unique ID for the new index
+  /// * `name` - human-readable name for the index
+  /// * `settings` - settings to apply to the new index
+  /// * `timeout` - maximum duration to wait, after which [`Error::DeadlineExceeded`](enum.Error.html#variant.DeadlineExceeded) is returned
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use std::time::Duration;
+  /// # use meilimelo::{prelude::*, Settings};
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let settings = Settings {
+  ///   searchable_attributes: Some(vec!["firstname".to_string(), "lastname".to_string()]),
+  ///   ..Default::default()
+  /// };
+  ///
+  /// let (index, status) = MeiliMelo::new("host")
+  ///   .create_index_configured_and_wait("employees", "Employees", &settings, Duration::from_secs(30))
+  ///   .await
+  ///   .unwrap();
+  ///
+  /// if let Some(error) = status.error() {
+  ///   println!("settings of {} were not applied: {}", index.uid, error);
+  /// }
+  /// # }
+  /// ```
+  #[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "tokio-runtime", feature = "async-std-runtime")
+  ))]
+  pub async fn create_index_configured_and_wait(
+    &'m self,
+    uid: &str,
+    name: &str,
+    settings: &Settings,
+    timeout: Duration,
+  ) -> Result<(Index, UpdateStatus), Error> {
+    let (index, update) = self.create_index_configured(uid, name, settings).await?;
+
+    updates::wait_idle(self, uid, timeout).await?;
+
+    Ok((index, update.status(self).await?))
+  }
+
+  /// Get the settings of an index
+  ///
+  /// # Arguments
+  ///
+  /// * `uid` - unique ID of the index
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let settings = MeiliMelo::new("host")
+  ///   .get_settings("employees")
+  ///   .await
+  ///   .unwrap();
+  ///
+  /// println!("{:?}", settings.ranking_rules);
+  /// # }
+  /// ```
+  pub async fn get_settings(&'m self, uid: &str) -> Result<Settings, Error> {
+    settings::get(self, uid).await
+  }
+
+  /// Get the settings of an index as untyped JSON
+  ///
+  /// Unlike [`get_settings()`](#method.get_settings), every setting
+  /// returned by MeiliSearch is kept, including those that are not modeled
+  /// by [`Settings`](struct.Settings.html) yet, such as experimental ones.
+  ///
+  /// # Arguments
+  ///
+  /// * `uid` - unique ID of the index
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let settings = MeiliMelo::new("host")
+  ///   .get_settings_raw("employees")
+  ///   .await
+  ///   .unwrap();
+  ///
+  /// println!("{}", settings["typoTolerance"]);
+  /// # }
+  /// ```
+  pub async fn get_settings_raw(&'m self, uid: &str) -> Result<serde_json::Value, Error> {
+    settings::get(self, uid).await
+  }
+
+  /// Update the settings of an index
+  ///
+  /// Only the attributes set in `settings` are updated.
+  ///
+  /// # Arguments
+  ///
+  /// * `uid` - unique ID of the index
+  /// * `settings` - settings to apply to the index
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::{prelude::*, Settings};
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let settings = Settings {
+  ///   distinct_attribute: Some("id".to_string()),
+  ///   ..Default::default()
+  /// };
+  ///
+  /// MeiliMelo::new("host")
+  ///   .update_settings("employees", &settings)
+  ///   .await;
+  /// # }
+  /// ```
+  pub async fn update_settings(&'m self, uid: &str, settings: &Settings) -> Result<Update, Error> {
+    settings::update(self, uid, settings).await
+  }
+
+  /// Reset all the settings of an index to their default values
+  ///
+  /// # Arguments
+  ///
+  /// * `uid` - unique ID of the index
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// MeiliMelo::new("host")
+  ///   .reset_settings("employees")
+  ///   .await;
+  /// # }
+  /// ```
+  pub async fn reset_settings(&'m self, uid: &str) -> Result<Update, Error> {
+    settings::reset(self, uid).await
+  }
+
+  /// Change the human-readable name of an existing index
+  ///
+  /// Only versions of MeiliSearch that still expose index names support
+  /// this. Instances that ignore the name answer with the index unchanged,
+  /// so the name of the returned index should be checked, while those that
+  /// reject it fail with an `InvalidQuery` error.
+  ///
+  /// # Arguments
+  ///
+  /// * `uid` - unique ID of the index to rename
+  /// * `name` - new human-readable name for the index
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let index = MeiliMelo::new("host")
+  ///   .rename_index("employees", "Staff")
+  ///   .await
+  ///   .unwrap();
+  ///
+  /// if index.name != "Staff" {
+  ///   println!("the instance does not support renaming indices");
+  /// }
+  /// # }
+  /// ```
+  pub async fn rename_index(&'m self, uid: &str, name: &str) -> Result<Index, Error> {
+    indices::rename(self, uid, name).await
+  }
+
+  /// Get statistics about the instance and all its indices
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let stats = MeiliMelo::new("host").stats().await.unwrap();
+  ///
+  /// println!("{} bytes, last updated at {:?}", stats.database_size, stats.last_update);
+  /// # }
+  /// ```
+  pub async fn stats(&'m self) -> Result<Stats, Error> {
+    stats::global(self).await
+  }
+
+  /// Get the version of the instance
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let version = MeiliMelo::new("host").version().await.unwrap();
+  ///
+  /// assert_eq!(version.pkg_version, "0.20.0");
+  /// # }
+  /// ```
+  pub async fn version(&'m self) -> Result<Version, Error> {
+    version::get(self).await
+  }
+
+  /// Get statistics about all indices in a single request
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// for (index, stats) in MeiliMelo::new("host").all_index_stats().await.unwrap() {
+  ///   println!("{}: {} documents", index, stats.number_of_documents);
+  /// }
+  /// # }
+  /// ```
+  pub async fn all_index_stats(&'m self) -> Result<HashMap<String, IndexStats>, Error> {
+    Ok(stats::global(self).await?.indexes)
+  }
+
+  /// Get statistics about an index
+  ///
+  /// # Arguments
+  ///
+  /// * `uid` - unique ID of the index
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let stats = MeiliMelo::new("host").index_stats("employees").await.unwrap();
+  ///
+  /// println!("{} documents", stats.number_of_documents);
+  /// # }
+  /// ```
+  pub async fn index_stats(&'m self, uid: &str) -> Result<IndexStats, Error> {
+    stats::index(self, uid).await
+  }
+
+  /// Get the number of documents containing each attribute of an index
+  ///
+  /// This is read from the statistics of the index, and can be used to
+  /// detect unexpected attributes without scanning the documents.
+  ///
+  /// # Arguments
+  ///
+  /// * `uid` - unique ID of the index
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let fields = MeiliMelo::new("host").field_distribution("employees").await.unwrap();
+  ///
+  /// for (field, count) in fields {
+  ///   println!("{}: {} documents", field, count);
+  /// }
+  /// # }
+  /// ```
+  pub async fn field_distribution(&'m self, uid: &str) -> Result<HashMap<String, i64>, Error> {
+    Ok(stats::index(self, uid).await?.field_distribution)
+  }
+
+  /// Check whether an index is currently being updated
+  ///
+  /// # Arguments
+  ///
+  /// * `uid` - unique ID of the index
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// if !MeiliMelo::new("host").is_indexing("employees").await.unwrap() {
+  ///   println!("index is idle");
+  /// }
+  /// # }
+  /// ```
+  pub async fn is_indexing(&'m self, uid: &str) -> Result<bool, Error> {
+    Ok(stats::index(self, uid).await?.is_indexing)
+  }
+
+  /// Delete an existing index
+  ///
+  /// # Arguments
+  ///
+  /// * `uid` - unique ID to the index to be deleted
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// MeiliMelo::new("host")
+  ///   .delete_index("employees")
+  ///   .await;
+  /// # }
+  /// ```
+  pub async fn delete_index(&'m self, uid: &str) -> Result<(), Error> {
+    indices::delete(self, uid).await
+  }
+
+  /// Index a collection of documents into MeiliSearch
+  ///
+  /// # Arguments
+  ///
+  /// * index - Name of the index into which documents are to be inserted
+  /// * documents - Slice of `Serialize`-able structs to insert, which can be borrowed from a `Vec` or an array
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[derive(serde::Serialize)]
+  /// # struct Employee { firstname: String, lastname: String }
+  /// #
+  /// let docs = vec![
+  ///   Employee { firstname: "Luke".to_string(), lastname: "Skywalker".to_string() }
+  /// ];
+  ///
+  /// MeiliMelo::new("host")
+  ///   .insert("employees", &docs);
+  /// ```
+  pub async fn insert<T>(&'m self, index: &str, documents: &[T]) -> Result<Update, Error>
+  where
+    T: Serialize,
+  {
+    documents::insert(self, index, documents).await
+  }
+
+  /// Index documents into MeiliSearch, either replacing or updating existing ones
+  ///
+  /// With [`InsertMode::Replace`](enum.InsertMode.html#variant.Replace),
+  /// this is equivalent to [`insert()`](#method.insert): a document sharing
+  /// its primary key with an existing one replaces it entirely. With
+  /// [`InsertMode::Update`](enum.InsertMode.html#variant.Update), only the
+  /// attributes present in the new document are overwritten. In both cases,
+  /// new documents are added.
+  ///
+  /// # Arguments
+  ///
+  /// * index - Name of the index into which documents are to be inserted
+  /// * documents - Slice of `Serialize`-able structs to insert
+  /// * mode - Whether existing documents are replaced or updated
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::{prelude::*, InsertMode};
+  /// #
+  /// # #[derive(serde::Serialize)]
+  /// # struct Employee { firstname: String, lastname: String }
+  /// #
+  /// let docs = vec![
+  ///   Employee { firstname: "Luke".to_string(), lastname: "Skywalker".to_string() }
+  /// ];
+  ///
+  /// MeiliMelo::new("host")
+  ///   .insert_with_mode("employees", &docs, InsertMode::Update);
+  /// ```
+  pub async fn insert_with_mode<T>(&'m self, index: &str, documents: &[T], mode: InsertMode) -> Result<Update, Error>
+  where
+    T: Serialize,
+  {
+    match mode {
+      InsertMode::Replace => documents::insert(self, index, documents).await,
+      InsertMode::Update => documents::update(self, index, documents, None).await,
+    }
+  }
+
+  /// Set attributes on several documents identified by their primary key
+  ///
+  /// The primary key of the index is looked up first, and the updates are
+  /// then sent as partial documents, relying on MeiliSearch merging them
+  /// into the existing documents: attributes that are not set are left
+  /// untouched. Documents that do not exist are created with only the set
+  /// attributes.
+  ///
+  /// # Arguments
+  ///
+  /// * index - Name of the index containing the documents
+  /// * updates - Attributes to set on each document
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::{prelude::*, PartialUpdate};
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let updates: Vec<PartialUpdate> = vec!["lskywalker", "hsolo"]
+  ///   .into_iter()
+  ///   .map(|id| PartialUpdate::new(id).set("status", "archived"))
+  ///   .collect();
+  ///
+  /// MeiliMelo::new("host").patch_documents("employees", &updates).await;
+  /// # }
+  /// ```
+  pub async fn patch_documents(&'m self, index: &str, updates: &[PartialUpdate]) -> Result<Update, Error> {
+    documents::patch(self, index, updates).await
+  }
+
+  /// Index documents produced by an asynchronous stream
+  ///
+  /// Documents are consumed from the stream and sent to MeiliSearch as
+  /// NDJSON in batches of `batch_size`, so that they never all need to be
+  /// held in memory. One operation is enqueued per batch, and the stream
+  /// stops being consumed at the first error, leaving the batches already
+  /// sent enqueued.
+  ///
+  /// # Arguments
+  ///
+  /// * `index` - name of the index into which documents are to be inserted
+  /// * `documents` - stream of `Serialize`-able structs to insert
+  /// * `batch_size` - maximum number of documents sent in a request
+  ///
+  /// A `batch_size` of zero is rejected with a `ValidationError`, and
+  /// documents that cannot be serialized fail with a `SerializeError`.
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[derive(serde::Serialize)]
+  /// # struct Employee { firstname: String, lastname: String }
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let docs = futures::stream::iter(vec![
+  ///   Employee { firstname: "Luke".to_string(), lastname: "Skywalker".to_string() }
+  /// ]);
+  ///
+  /// let updates = MeiliMelo::new("host")
+  ///   .insert_stream("employees", docs, 1000)
+  ///   .await;
+  /// # }
+  /// ```
+  pub async fn insert_stream<T, S>(&'m self, index: &str, documents: S, batch_size: usize) -> Result<Vec<Update>, Error>
+  where
+    T: Serialize,
+    S: Stream<Item = T>,
+  {
+    documents::insert_stream(self, index, documents, batch_size).await
+  }
+
+  /// Index a single document into MeiliSearch
+  ///
+  /// If a document with the same primary key already exists, it is replaced.
+  ///
+  /// # Arguments
+  ///
+  /// * index - Name of the index into which the document is to be inserted
+  /// * document - `Serialize`-able struct to insert
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[derive(serde::Serialize)]
+  /// # struct Employee { firstname: String, lastname: String }
+  /// #
+  /// let doc = Employee { firstname: "Luke".to_string(), lastname: "Skywalker".to_string() };
+  ///
+  /// MeiliMelo::new("host")
+  ///   .upsert_document("employees", &doc);
+  /// ```
+  pub async fn upsert_document<T>(&'m self, index: &str, document: &T) -> Result<Update, Error>
+  where
+    T: Serialize,
+  {
+    documents::insert(self, index, std::slice::from_ref(document)).await
+  }
+
+  /// Partially update a single document
+  ///
+  /// Only the provided attributes are updated, the other attributes of an
+  /// existing document are left untouched. If the document does not exist,
+  /// it is created.
+  ///
+  /// # Arguments
+  ///
+  /// * index - Name of the index containing the document
+  /// * document - `Serialize`-able struct containing the primary key and the attributes to update
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[derive(serde::Serialize)]
+  /// # struct EmployeeRole { id: String, role: String }
+  /// #
+  /// let doc = EmployeeRole { id: "lskywalker".to_string(), role: "Jedi".to_string() };
+  ///
+  /// MeiliMelo::new("host")
+  ///   .update_document("employees", &doc);
+  /// ```
+  pub async fn update_document<T>(&'m self, index: &str, document: &T) -> Result<Update, Error>
+  where
+    T: Serialize,
+  {
+    documents::update(self, index, std::slice::from_ref(document), None).await
+  }
+
+  /// Partially update documents
+  ///
+  /// Only the provided attributes are updated, the other attributes of
+  /// existing documents are left untouched, and documents that do not exist
+  /// are created. If the index has no primary key yet, the given one is set,
+  /// otherwise MeiliSearch infers it from the documents.
+  ///
+  /// # Arguments
+  ///
+  /// * index - Name of the index containing the documents
+  /// * documents - Slice of `Serialize`-able structs containing the primary key and the attributes to update
+  /// * primary_key - Primary key to set on the index if it has none
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[derive(serde::Serialize)]
+  /// # struct EmployeeRole { id: String, role: String }
+  /// #
+  /// let docs = vec![EmployeeRole { id: "lskywalker".to_string(), role: "Jedi".to_string() }];
+  ///
+  /// MeiliMelo::new("host")
+  ///   .update_documents("employees", &docs, Some("id"));
+  /// ```
+  pub async fn update_documents<T>(
+    &'m self,
+    index: &str,
+    documents: &[T],
+    primary_key: Option<&str>,
+  ) -> Result<Update, Error>
+  where
+    T: Serialize,
+  {
+    documents::update(self, index, documents, primary_key).await
+  }
+
+  /// List documents in order
+  ///
+  /// MeiliSearch returns at most 1000 documents per request, so larger
+  /// limits are split into several consecutive requests, whose documents are
+  /// concatenated. Fewer than `limit` documents are returned once the end of
+  /// the index is reached. A `limit` of zero (or less) sends no request and
+  /// returns no document, rather than the default page of MeiliSearch.
+  ///
+  /// # Arguments
+  ///
+  /// * `index` - name of the index to browse
+  /// * `limit` - number of documents to return
+  /// * `offset` - offset to the first document to return
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[derive(serde::Deserialize)]
+  /// # struct Employee { firstname: String, lastname: String };
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let meili = MeiliMelo::new("host");
+  ///
+  /// for document in &meili.list_documents::<Employee>("employees", 10, 0).await.unwrap() {
+  ///   println!("{} {}", document.firstname, document.lastname);
+  /// }
+  /// # }
+  /// ```
+  pub async fn list_documents<R>(&'m self, index: &str, limit: i64, offset: i64) -> Result<Vec<R>, Error>
+  where
+    for<'de> R: Deserialize<'de>,
+  {
+    documents::list(self, index, limit, offset).await
+  }
+
+  /// List documents with arbitrary query string parameters
+  ///
+  /// Parameters are URL-encoded and sent as-is, which allows to use
+  /// parameters not otherwise supported by this crate.
   ///
   /// # Arguments
   ///
-  /// * `host` - Scheme, hostname and port to the MeiliSearch instance
-  pub fn new(host: &str) -> MeiliMelo {
-    MeiliMelo {
-      host,
-      ..Default::default()
-    }
-  }
-
-  pub(crate) fn request(&self, method: Method, path: &str) -> RequestBuilder {
-    let url = format!("{}{}", self.host, path);
-
-    match self.secret_key {
-      Some(key) => Client::new().request(method, &url).header("X-Meili-API-Key", key),
-      None => Client::new().request(method, &url),
-    }
+  /// * `index` - name of the index to browse
+  /// * `params` - names and values of the query string parameters
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[derive(serde::Deserialize)]
+  /// # struct Employee { firstname: String, lastname: String };
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let meili = MeiliMelo::new("host");
+  /// let params = [("limit", "10"), ("fields", "firstname,lastname")];
+  ///
+  /// for document in &meili.list_documents_with::<Employee>("employees", &params).await.unwrap() {
+  ///   println!("{} {}", document.firstname, document.lastname);
+  /// }
+  /// # }
+  /// ```
+  pub async fn list_documents_with<R>(&'m self, index: &str, params: &[(&str, &str)]) -> Result<Vec<R>, Error>
+  where
+    for<'de> R: Deserialize<'de>,
+  {
+    documents::list_with(self, index, params).await
   }
 
-  /// Adds the secret key to be used to authenticate against MeiliSearch
+  /// Iterate synchronously over all the documents of an index
+  ///
+  /// Documents are fetched in pages of `page_size` documents as the iterator
+  /// is consumed. This requires the `blocking` feature, and must not be
+  /// called from within an async runtime.
   ///
   /// # Arguments
   ///
-  /// * `key` - The string representation of the secret key
+  /// * `index` - name of the index to browse
+  /// * `page_size` - number of documents to fetch in each request, capped at 1000
   ///
   /// # Examples
   ///
-  /// ```
-  /// use meilimelo::prelude::*;
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[derive(serde::Deserialize)]
+  /// # struct Employee { firstname: String, lastname: String };
+  /// #
+  /// let meili = MeiliMelo::new("host");
   ///
-  /// let m = MeiliMelo::new("https://meilisearch.example.com:7700")
-  ///   .with_secret_key("abcdef");
+  /// for document in meili.documents_iter::<Employee>("employees", 100) {
+  ///   let document = document.unwrap();
+  ///
+  ///   println!("{} {}", document.firstname, document.lastname);
+  /// }
   /// ```
-  pub fn with_secret_key(mut self, key: &'m str) -> MeiliMelo<'m> {
-    self.secret_key = Some(key);
-    self
+  #[cfg(feature = "blocking")]
+  pub fn documents_iter<R>(&'m self, index: &str, page_size: i64) -> DocumentsIter<'m, R>
+  where
+    for<'de> R: Deserialize<'de>,
+  {
+    DocumentsIter::new(self, index, page_size)
   }
 
-  /// Initialize a search query
+  /// Fetch documents through a request body
   ///
-  /// The returned struct implements the builder pattern and allows to
-  /// construct the query incrementally. Please see
-  /// [`Query`](search/struct.Query.html) for details on the available methods.
+  /// Unlike [`list_documents`](#method.list_documents), parameters are sent
+  /// in the body of a `POST` request, which avoids running into URL length
+  /// limits with long field lists or filters.
   ///
   /// # Arguments
   ///
-  /// * `index` - The name of the index to search
-  pub fn search(&'m self, index: &'m str) -> Query<'_> {
-    Query::new(self, index)
-  }
-
-  /// List all available indices
+  /// * `index` - name of the index to browse
+  /// * `query` - parameters of the fetch
   ///
   /// # Examples
   ///
   /// ```no_run
-  /// # use meilimelo::prelude::*;
+  /// # use meilimelo::{prelude::*, DocumentsFetch};
+  /// #
+  /// # #[derive(serde::Deserialize)]
+  /// # struct Employee { firstname: String, lastname: String };
   /// #
   /// # #[tokio::main]
   /// # async fn main() {
   /// let meili = MeiliMelo::new("host");
+  /// let query = DocumentsFetch {
+  ///   limit: Some(10),
+  ///   fields: Some(&["firstname", "lastname"]),
+  ///   filter: Some("company = ACME"),
+  ///   ..Default::default()
+  /// };
   ///
-  /// for index in meili.indices().await.unwrap() {
-  ///   println!("{}", index.name);
+  /// for document in &meili.fetch_documents::<Employee>("employees", &query).await.unwrap().results {
+  ///   println!("{} {}", document.firstname, document.lastname);
   /// }
   /// # }
   /// ```
-  pub async fn indices(&'m self) -> Result<Vec<Index>, Error> {
-    indices::list(self).await
+  pub async fn fetch_documents<R>(&'m self, index: &str, query: &DocumentsFetch<'_>) -> Result<DocumentsPage<R>, Error>
+  where
+    for<'de> R: Deserialize<'de>,
+  {
+    documents::fetch(self, index, query).await
   }
 
-  /// Create a new index
+  /// List documents in order
   ///
   /// # Arguments
   ///
-  /// * `uid` - unique ID for the new index
-  /// * `name` - human-readable name for the index
+  /// * `index` - name of the index to browse
+  /// * `uid` - Unique ID of the document to return, either a string or a number
   ///
   /// # Examples
   ///
-  /// ```
-  /// # use meilimelo::prelude::*;
+  /// ```no_run
+  /// # use meilimelo::{prelude::*, Error};
+  /// #
+  /// # #[derive(serde::Deserialize)]
+  /// # struct Employee;
   /// #
   /// # #[tokio::main]
   /// # async fn main() {
-  /// MeiliMelo::new("host")
-  ///   .create_index("employees", "Employees")
+  /// MeiliMelo::new("")
+  ///   .get_document::<Employee>("employees", "lskywalker")
   ///   .await;
   /// # }
   /// ```
-  pub async fn create_index<'a>(&'m self, uid: &str, name: &str) -> Result<Index, Error> {
-    indices::create(self, uid, name).await
+  pub async fn get_document<R>(&'m self, index: &str, uid: impl Display) -> Result<R, Error>
+  where
+    for<'de> R: Deserialize<'de>,
+  {
+    documents::get(self, index, &uid.to_string()).await
   }
 
-  /// Delete an existing index
+  /// Get several documents by their unique IDs in a single request
+  ///
+  /// The primary key of the index is looked up first, and the documents are
+  /// then fetched with a filter on it, which requires the primary key to be
+  /// a filterable attribute. IDs that do not match any document are ignored,
+  /// and the documents are not guaranteed to be returned in the order of
+  /// `uids`.
   ///
   /// # Arguments
   ///
-  /// * `uid` - unique ID to the index to be deleted
+  /// * `index` - name of the index to browse
+  /// * `uids` - Unique IDs of the documents to return, either strings or numbers
   ///
   /// # Examples
   ///
-  /// ```
+  /// ```no_run
   /// # use meilimelo::prelude::*;
   /// #
+  /// # #[derive(serde::Deserialize)]
+  /// # struct Employee { firstname: String, lastname: String };
+  /// #
   /// # #[tokio::main]
   /// # async fn main() {
-  /// MeiliMelo::new("host")
-  ///   .delete_index("employees")
+  /// let employees = MeiliMelo::new("host")
+  ///   .get_documents::<Employee, _>("employees", &["lskywalker", "hsolo"])
   ///   .await;
   /// # }
   /// ```
-  pub async fn delete_index(&'m self, uid: &str) -> Result<(), Error> {
-    indices::delete(self, uid).await
+  pub async fn get_documents<R, U>(&'m self, index: &str, uids: &[U]) -> Result<Vec<R>, Error>
+  where
+    for<'de> R: Deserialize<'de>,
+    U: Display,
+  {
+    let uids: Vec<String> = uids.iter().map(ToString::to_string).collect();
+
+    documents::get_many(self, index, &uids).await
   }
 
-  /// Index a collection of documents into MeiliSearch
+  /// Get a document that may not exist
+  ///
+  /// Contrary to [`get_document`](#method.get_document), a missing document
+  /// is not considered an error and returns `None`.
   ///
   /// # Arguments
   ///
-  /// * index - Name of the index into which documents are to be inserted
-  /// * documents - Collection of `Serialize`-able structs to insert
+  /// * `index` - name of the index to browse
+  /// * `uid` - Unique ID of the document to return, either a string or a number
   ///
   /// # Examples
   ///
-  /// ```
+  /// ```no_run
   /// # use meilimelo::prelude::*;
   /// #
-  /// # #[derive(serde::Serialize)]
-  /// # struct Employee { firstname: String, lastname: String }
+  /// # #[derive(serde::Deserialize)]
+  /// # struct Employee { firstname: String, lastname: String };
   /// #
-  /// let docs = vec![
-  ///   Employee { firstname: "Luke".to_string(), lastname: "Skywalker".to_string() }
-  /// ];
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let meili = MeiliMelo::new("host");
   ///
-  /// MeiliMelo::new("host")
-  ///   .insert("employees", &docs);
+  /// match meili.get_document_opt::<Employee>("employees", "lskywalker").await.unwrap() {
+  ///   Some(document) => println!("{} {}", document.firstname, document.lastname),
+  ///   None => println!("not found"),
+  /// }
+  /// # }
   /// ```
-  pub async fn insert<T>(&'m self, index: &str, documents: &Vec<T>) -> Result<Update, Error>
+  pub async fn get_document_opt<R>(&'m self, index: &str, uid: impl Display) -> Result<Option<R>, Error>
   where
-    T: Serialize,
+    for<'de> R: Deserialize<'de>,
   {
-    documents::insert(self, index, documents).await
+    documents::get_opt(self, index, &uid.to_string()).await
   }
 
-  /// List documents in order
+  /// Get a document unless it was not modified since it was last read
+  ///
+  /// When given the entity tag of a previous read, it is sent to MeiliSearch
+  /// in an `If-None-Match` header, and `None` is returned if the server
+  /// reports the document as unchanged, in which case a cached copy can be
+  /// reused. If the server does not emit entity tags, the document is always
+  /// returned.
   ///
   /// # Arguments
   ///
   /// * `index` - name of the index to browse
-  /// * `limit` - number of documents to return
-  /// * `offset` - offset to the first document to return
+  /// * `uid` - Unique ID of the document to return, either a string or a number
+  /// * `etag` - entity tag returned by a previous read of the document
   ///
   /// # Examples
   ///
@@ -221,53 +1870,113 @@ impl<'m> MeiliMelo<'m> {
   /// # #[tokio::main]
   /// # async fn main() {
   /// let meili = MeiliMelo::new("host");
+  /// let cached = meili.get_document_if_modified::<Employee>("employees", "lskywalker", None).await.unwrap();
+  /// let etag = cached.as_ref().and_then(|cached| cached.etag.as_deref());
   ///
-  /// for document in &meili.list_documents::<Employee>("employees", 10, 0).await.unwrap() {
-  ///   println!("{} {}", document.firstname, document.lastname);
+  /// match meili.get_document_if_modified::<Employee>("employees", "lskywalker", etag).await.unwrap() {
+  ///   Some(fresh) => println!("{} {}", fresh.document.firstname, fresh.document.lastname),
+  ///   None => println!("not modified"),
   /// }
   /// # }
   /// ```
-  pub async fn list_documents<R>(&'m self, index: &str, limit: i64, offset: i64) -> Result<Vec<R>, Error>
+  pub async fn get_document_if_modified<R>(
+    &'m self,
+    index: &str,
+    uid: impl Display,
+    etag: Option<&str>,
+  ) -> Result<Option<TaggedDocument<R>>, Error>
   where
     for<'de> R: Deserialize<'de>,
   {
-    documents::list(self, index, limit, offset).await
+    documents::get_if_modified(self, index, &uid.to_string(), etag).await
   }
 
-  /// List documents in order
+  /// Edit documents in place with a RHAI function
+  ///
+  /// This requires the `editDocumentsByFunction` experimental feature to be
+  /// enabled on the MeiliSearch instance, and the `experimental` feature to
+  /// be enabled on this crate.
   ///
   /// # Arguments
   ///
-  /// * `index` - name of the index to browse
-  /// * `uid` - Unique ID of the document to return
+  /// * `index` - name of the index containing the documents
+  /// * `filter` - filter expression selecting the documents to edit, all documents are edited if `None`
+  /// * `function` - RHAI function applied to each selected document
+  /// * `context` - data made available to the function as `context`
   ///
   /// # Examples
   ///
   /// ```no_run
-  /// # use meilimelo::{prelude::*, Error};
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let context = serde_json::json!({ "category": "books" });
+  ///
+  /// MeiliMelo::new("host")
+  ///   .edit_documents("products", Some("category = Books"), "doc.category = context.category", Some(&context))
+  ///   .await;
+  /// # }
+  /// ```
+  #[cfg(feature = "experimental")]
+  pub async fn edit_documents(
+    &'m self,
+    index: &str,
+    filter: Option<&str>,
+    function: &str,
+    context: Option<&serde_json::Value>,
+  ) -> Result<Update, Error> {
+    documents::edit(self, index, filter, function, context).await
+  }
+
+  /// Get documents similar to a given one
+  ///
+  /// Similarity is computed from the vectors generated by the given
+  /// embedder, which must be configured on the index. This requires the
+  /// `experimental` feature to be enabled on this crate, and the vector
+  /// store feature on MeiliSearch instances older than v1.13.
+  ///
+  /// # Arguments
+  ///
+  /// * `index` - name of the index containing the documents
+  /// * `uid` - Unique ID of the reference document, either a string or a number
+  /// * `embedder` - name of the embedder used to compare documents
+  /// * `limit` - maximum number of documents to return, MeiliSearch's default if `None`
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
   /// #
   /// # #[derive(serde::Deserialize)]
-  /// # struct Employee;
+  /// # struct Product { name: String };
   /// #
   /// # #[tokio::main]
   /// # async fn main() {
-  /// MeiliMelo::new("")
-  ///   .get_document::<Employee>("employees", "lskywalker")
+  /// let related = MeiliMelo::new("host")
+  ///   .similar_documents::<Product>("products", 42, "default", Some(5))
   ///   .await;
   /// # }
   /// ```
-  pub async fn get_document<R>(&'m self, index: &str, uid: &str) -> Result<R, Error>
+  #[cfg(feature = "experimental")]
+  pub async fn similar_documents<R>(
+    &'m self,
+    index: &str,
+    uid: impl Display,
+    embedder: &str,
+    limit: Option<i64>,
+  ) -> Result<results::Results<R>, Error>
   where
     for<'de> R: Deserialize<'de>,
   {
-    documents::get(self, index, uid).await
+    similar::get(self, index, &uid.to_string(), embedder, limit).await
   }
 
   /// Delete a document
   ///
   /// # Arguments
   ///
-  /// * `uid` - Unique ID of the document to delete
+  /// * `uid` - Unique ID of the document to delete, either a string or a number
   ///
   /// # Examples
   ///
@@ -281,7 +1990,155 @@ impl<'m> MeiliMelo<'m> {
   ///   .await;
   /// # }
   /// ```
-  pub async fn delete_document(&'m self, index: &str, uid: &str) -> Result<Update, Error> {
-    documents::delete(self, index, uid).await
+  pub async fn delete_document(&'m self, index: &str, uid: impl Display) -> Result<Update, Error> {
+    documents::delete(self, index, &uid.to_string()).await
+  }
+
+  /// Wait until an index has no enqueued or processing operation
+  ///
+  /// The operations of the index are polled until all of them are processed
+  /// or failed, which is handy when several writes were issued, for example
+  /// in migrations or test teardowns. This requires either the
+  /// `tokio-runtime` (enabled by default) or the `async-std-runtime` feature.
+  ///
+  /// # Arguments
+  ///
+  /// * `index` - name of the index to watch
+  /// * `timeout` - maximum duration to wait, after which [`Error::DeadlineExceeded`](enum.Error.html#variant.DeadlineExceeded) is returned
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use std::time::Duration;
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// MeiliMelo::new("host")
+  ///   .wait_for_index_idle("employees", Duration::from_secs(30))
+  ///   .await
+  ///   .unwrap();
+  /// # }
+  /// ```
+  #[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "tokio-runtime", feature = "async-std-runtime")
+  ))]
+  pub async fn wait_for_index_idle(&'m self, index: &str, timeout: Duration) -> Result<(), Error> {
+    updates::wait_idle(self, index, timeout).await
+  }
+
+  /// Get the processing status of an asynchronous operation
+  ///
+  /// Once a deletion is processed, the number of removed documents can be
+  /// read through [`UpdateStatus::deleted_documents()`](struct.UpdateStatus.html#method.deleted_documents).
+  ///
+  /// # Arguments
+  ///
+  /// * `index` - name of the index the operation was performed on
+  /// * `id` - identifier of the operation, as returned in [`Update`](struct.Update.html)
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let meili = MeiliMelo::new("host");
+  /// let update = meili.delete_document("employees", "lskywalker").await.unwrap();
+  /// let status = meili.update_status("employees", update.id).await.unwrap();
+  ///
+  /// if let Some(count) = status.deleted_documents() {
+  ///   println!("removed {} stale records", count);
+  /// }
+  /// # }
+  /// ```
+  pub async fn update_status(&'m self, index: &str, id: i64) -> Result<UpdateStatus, Error> {
+    updates::get(self, index, id).await
+  }
+
+  /// Create an API key
+  ///
+  /// [`KeyBuilder`](struct.KeyBuilder.html) should be used to describe the key.
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - description of the key to create
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::{prelude::*, KeyBuilder};
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let request = KeyBuilder::new().allow_search().on_indexes(&["products"]).build();
+  /// let key = MeiliMelo::new("host").create_key(&request).await.unwrap();
+  ///
+  /// println!("{}", key.key);
+  /// # }
+  /// ```
+  pub async fn create_key(&'m self, key: &CreateKey) -> Result<Key, Error> {
+    keys::create(self, key).await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use reqwest::{header::CONTENT_TYPE, Method};
+
+  use super::{client::JsonBody, MeiliMelo};
+
+  #[test]
+  fn content_type() {
+    let meili = MeiliMelo::new("http://localhost:7700").with_content_type("application/json; charset=utf-8");
+
+    let request = meili
+      .request(Method::POST, "/indexes")
+      .json_body(&meili, &serde_json::json!({ "uid": "employees" }))
+      .build()
+      .unwrap();
+
+    let values: Vec<_> = request.headers().get_all(CONTENT_TYPE).iter().collect();
+
+    assert_eq!(values, vec!["application/json; charset=utf-8"]);
+    assert_eq!(
+      request.body().and_then(|body| body.as_bytes()),
+      Some(&br#"{"uid":"employees"}"#[..])
+    );
+
+    let request = meili.request(Method::GET, "/indexes").build().unwrap();
+
+    assert!(request.headers().get(CONTENT_TYPE).is_none());
+
+    let meili = MeiliMelo::new("http://localhost:7700");
+    let request = meili
+      .request(Method::POST, "/indexes")
+      .json_body(&meili, &serde_json::json!({}))
+      .build()
+      .unwrap();
+
+    assert_eq!(request.headers()[CONTENT_TYPE], "application/json");
+  }
+
+  #[test]
+  fn host_trailing_slash() {
+    for host in &["http://localhost:7700", "http://localhost:7700/"] {
+      let meili = MeiliMelo::new(host);
+      let request = meili.request(Method::GET, "/indexes").build().unwrap();
+
+      assert_eq!(meili.host, "http://localhost:7700");
+      assert_eq!(request.url().as_str(), "http://localhost:7700/indexes");
+    }
+  }
+
+  #[test]
+  fn new_owned() {
+    let meili = MeiliMelo::new_owned("http://localhost:7700/".to_string()).with_secret_key("master".to_string());
+    let request = meili.request(Method::GET, "/indexes").build().unwrap();
+
+    assert_eq!(request.url().as_str(), "http://localhost:7700/indexes");
+    assert_eq!(request.headers()["X-Meili-API-Key"], "master");
   }
 }
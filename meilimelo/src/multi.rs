@@ -0,0 +1,212 @@
+use reqwest::{Method, StatusCode};
+use serde_json::Value;
+
+use crate::{client::JsonBody, prelude::*, search::QueryError, Error};
+
+/// Utility to run several search queries in a single request
+///
+/// Queries can target different indices and return different document
+/// types. The results of each query are kept untyped until they are
+/// retrieved, with their own type, through
+/// [`MultiResults::take()`](struct.MultiResults.html#method.take).
+///
+/// # Examples
+///
+/// ```no_run
+/// # use meilimelo::prelude::*;
+/// #
+/// # #[meilimelo::schema]
+/// # struct Employee;
+/// #
+/// # #[meilimelo::schema]
+/// # struct Company;
+/// #
+/// # #[tokio::main]
+/// # async fn main() {
+/// let meili = MeiliMelo::new("host");
+///
+/// let mut results = meili
+///   .multi_search()
+///   .query(meili.search("employees").query("skywalker"))
+///   .query(meili.search("companies").query("rebellion"))
+///   .run()
+///   .await
+///   .unwrap();
+///
+/// let employees = results.take::<Employee>(0).unwrap().unwrap();
+/// let companies = results.take::<Company>(1).unwrap().unwrap();
+/// # }
+/// ```
+pub struct MultiSearch<'m> {
+  meili: &'m MeiliMelo<'m>,
+  queries: Vec<Query<'m>>,
+}
+
+/// Untyped results of a multi-search request
+///
+/// Results are stored in the order the queries were added.
+#[derive(Debug)]
+pub struct MultiResults {
+  results: Vec<Option<Results<Value>>>,
+}
+
+#[derive(Serialize)]
+struct MultiSearchBody<'a, 'm> {
+  queries: Vec<MultiSearchQuery<'a, 'm>>,
+}
+
+#[derive(Serialize)]
+struct MultiSearchQuery<'a, 'm> {
+  #[serde(rename = "indexUid")]
+  index: &'m str,
+  #[serde(flatten)]
+  query: &'a Query<'m>,
+}
+
+#[derive(Deserialize)]
+struct MultiSearchResponse {
+  results: Vec<Results<Value>>,
+}
+
+impl<'m> MultiSearch<'m> {
+  pub(crate) fn new(meili: &'m MeiliMelo<'m>) -> MultiSearch<'m> {
+    MultiSearch { meili, queries: vec![] }
+  }
+
+  /// Adds a query to the request
+  ///
+  /// The index targeted by the query is the one it was created with.
+  pub fn query(mut self, query: Query<'m>) -> MultiSearch<'m> {
+    self.queries.push(query);
+    self
+  }
+
+  /// Sends all the queries in a single request
+  ///
+  /// Since they share a request, queries authenticated with their own key
+  /// through [`Query::with_key()`](struct.Query.html#method.with_key) must
+  /// all use the same one, which then replaces the key of the instance.
+  /// Mixing keys is rejected with a `ValidationError`.
+  pub async fn run(mut self) -> Result<MultiResults, Error> {
+    for query in &mut self.queries {
+      query.validate()?;
+      query.clamp_limits();
+    }
+
+    let key = self.key()?;
+
+    let body = MultiSearchBody {
+      queries: self
+        .queries
+        .iter()
+        .map(|query| MultiSearchQuery {
+          index: query.index,
+          query,
+        })
+        .collect(),
+    };
+
+    let response = self
+      .meili
+      .request_with_key(Method::POST, "/multi-search", key.or(self.meili.secret_key.as_deref()))
+      .json_body(self.meili, &body)
+      .send()
+      .await
+      .map_err(|err| Error::UpstreamError(err))?;
+
+    match response.status() {
+      StatusCode::OK => {
+        let response = self.meili.json::<MultiSearchResponse>(response).await?;
+
+        Ok(MultiResults {
+          results: response.results.into_iter().map(Some).collect(),
+        })
+      }
+
+      _ => {
+        let error = self.meili.json::<QueryError>(response).await?;
+
+        Err(Error::InvalidQuery(error))
+      }
+    }
+  }
+
+  /// Key shared by the queries, if they were given one
+  fn key(&self) -> Result<Option<&'m str>, Error> {
+    let key = self.queries.first().and_then(|query| query.key);
+
+    if self.queries.iter().any(|query| query.key != key) {
+      return Err(Error::ValidationError(
+        "all queries of a multi-search must use the same key".to_string(),
+      ));
+    }
+
+    Ok(key)
+  }
+}
+
+impl MultiResults {
+  /// Number of queries the results were returned for
+  pub fn len(&self) -> usize {
+    self.results.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.results.is_empty()
+  }
+
+  /// Takes the results of a query, deserializing its hits into `R`
+  ///
+  /// Returns `None` if there is no query at that position, or if its results
+  /// were already taken.
+  ///
+  /// # Arguments
+  ///
+  /// * `position` - position of the query in the request, starting at zero
+  pub fn take<R>(&mut self, position: usize) -> Option<Result<Results<R>, Error>>
+  where
+    for<'de> R: Deserialize<'de>,
+  {
+    let results = self.results.get_mut(position)?.take()?;
+
+    Some(typed(results))
+  }
+}
+
+fn typed<R>(mut results: Results<Value>) -> Result<Results<R>, Error>
+where
+  for<'de> R: Deserialize<'de>,
+{
+  let hits = results
+    .results
+    .drain(..)
+    .map(serde_json::from_value::<R>)
+    .collect::<Result<Vec<R>, _>>()
+    .map_err(|err| Error::ParseError(Box::new(err)))?;
+
+  Ok(results.with_results(hits))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{prelude::*, Error};
+
+  #[test]
+  fn key() {
+    let meili = MeiliMelo::new("http://localhost:7700");
+
+    let multi = meili
+      .multi_search()
+      .query(meili.search("employees").with_key("tenant"))
+      .query(meili.search("companies").with_key("tenant"));
+
+    assert_eq!(multi.key().unwrap(), Some("tenant"));
+
+    let multi = meili
+      .multi_search()
+      .query(meili.search("employees").with_key("tenant"))
+      .query(meili.search("companies"));
+
+    assert!(matches!(multi.key(), Err(Error::ValidationError(_))));
+  }
+}
@@ -1,23 +1,174 @@
-use std::{collections::HashMap, iter::IntoIterator};
+use std::{collections::HashMap, hash::Hash, iter::IntoIterator, ops::Index, time::Duration};
+
+use serde_json::Value;
 
 #[derive(Debug, Deserialize)]
 pub struct Results<T> {
+    #[serde(default)]
     pub query: String,
-    #[serde(rename = "exhaustiveNbHits")]
+    #[serde(rename = "exhaustiveNbHits", default)]
     pub exhaustive_hits: bool,
-    #[serde(rename = "nbHits")]
+    #[serde(rename = "nbHits", alias = "estimatedTotalHits", alias = "totalHits", default)]
     pub hits: i64,
     #[serde(rename = "exhaustiveFacetsCount")]
     pub exhaustive_facets: Option<bool>,
-    #[serde(rename = "facetsDistribution")]
+    #[serde(rename = "facetsDistribution", alias = "facetDistribution")]
     pub distribution: Option<HashMap<String, HashMap<String, i64>>>,
+    /// Maximum number of hits, absent from page-based results
+    #[serde(default)]
     pub limit: i64,
+    /// Number of skipped hits, absent from page-based results
+    #[serde(default)]
     pub offset: i64,
+    /// Current page, for results requested through `page()` or `hits_per_page()`
+    pub page: Option<i64>,
+    /// Number of hits per page, for page-based results
+    #[serde(rename = "hitsPerPage")]
+    pub hits_per_page: Option<i64>,
+    /// Total number of pages, for page-based results
+    #[serde(rename = "totalPages")]
+    pub total_pages: Option<i64>,
     #[serde(rename = "processingTimeMs")]
     pub duration: i64,
 
     #[serde(rename = "hits")]
     pub results: Vec<T>,
+
+    /// Duration of the search observed by the client, when timing is enabled
+    #[serde(skip)]
+    pub(crate) client_duration: Option<Duration>,
+}
+
+/// Score computed by a ranking rule for a hit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankingRuleScore {
+    /// Position of the rule in the ranking rules of the index
+    pub order: i64,
+    /// Score of the hit for this rule, between 0 and 1
+    pub score: Option<f64>,
+    /// Rule-specific details, such as the number of matching words or typos
+    #[serde(flatten)]
+    pub details: HashMap<String, Value>,
+}
+
+/// Breakdown of the ranking score of a hit, keyed by ranking rule
+///
+/// It is returned in the `_rankingScoreDetails` attribute of each hit when
+/// [`Query::show_ranking_score_details()`](struct.Query.html#method.show_ranking_score_details)
+/// is enabled, and can be added to a schema as follows:
+///
+/// ```
+/// use meilimelo::RankingScoreDetails;
+///
+/// #[meilimelo::schema]
+/// struct Employee {
+///   firstname: String,
+///   #[serde(rename = "_rankingScoreDetails", default)]
+///   ranking: Option<RankingScoreDetails>,
+/// }
+/// ```
+pub type RankingScoreDetails = HashMap<String, RankingRuleScore>;
+
+impl<T> Results<T> {
+    /// Replaces the hits while keeping the metadata of the results
+    pub(crate) fn with_results<U>(self, results: Vec<U>) -> Results<U> {
+        Results {
+            query: self.query,
+            exhaustive_hits: self.exhaustive_hits,
+            hits: self.hits,
+            exhaustive_facets: self.exhaustive_facets,
+            distribution: self.distribution,
+            limit: self.limit,
+            offset: self.offset,
+            page: self.page,
+            hits_per_page: self.hits_per_page,
+            total_pages: self.total_pages,
+            duration: self.duration,
+            results,
+            client_duration: self.client_duration,
+        }
+    }
+
+    /// Duration of the search observed by the client, from sending the request to parsing the response
+    ///
+    /// This is only measured when timing is enabled with
+    /// [`MeiliMelo::with_timing()`](struct.MeiliMelo.html#method.with_timing),
+    /// and can be compared with the processing time reported by MeiliSearch
+    /// to estimate the time spent on the network.
+    pub fn client_duration(&self) -> Option<Duration> {
+        self.client_duration
+    }
+
+    /// Number of hits contained in this page of results
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Whether this page of results contains no hit
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Whether the number of hits reached the `maxTotalHits` setting of the index
+    ///
+    /// When it does, the reported number of hits is truncated and more
+    /// documents may match the query than can be reached through pagination.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_total_hits` - value of the `maxTotalHits` pagination setting of the index
+    pub fn is_capped(&self, max_total_hits: i64) -> bool {
+        self.hits >= max_total_hits
+    }
+
+    /// Values of the given facet with their number of hits, sorted by descending count
+    ///
+    /// Values with the same count are sorted alphabetically. An empty `Vec`
+    /// is returned if the facet distribution was not requested or does not
+    /// contain the facet.
+    ///
+    /// # Arguments
+    ///
+    /// * `facet` - name of the faceted attribute
+    pub fn facet_distribution_sorted(&self, facet: &str) -> Vec<(String, i64)> {
+        let mut values: Vec<(String, i64)> = self
+            .distribution
+            .as_ref()
+            .and_then(|distribution| distribution.get(facet))
+            .map(|values| values.iter().map(|(value, count)| (value.clone(), *count)).collect())
+            .unwrap_or_default();
+
+        values.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+        values
+    }
+
+    /// Only keeps the hits matching the given predicate
+    ///
+    /// The total number of hits is decreased by the number of dropped hits.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - function returning whether a hit should be kept
+    pub fn retain<F>(&mut self, predicate: F)
+    where
+        F: Fn(&T) -> bool,
+    {
+        let count = self.results.len();
+
+        self.results.retain(|hit| predicate(hit));
+        self.hits -= (count - self.results.len()) as i64;
+    }
+
+    /// Consumes the results into a map of hits keyed by the value extracted by `key`
+    ///
+    /// If several hits share the same key, only the last one is kept.
+    pub fn into_hashmap<K, F>(self, key: F) -> HashMap<K, T>
+    where
+        K: Eq + Hash,
+        F: Fn(&T) -> K,
+    {
+        self.results.into_iter().map(|hit| (key(&hit), hit)).collect()
+    }
 }
 
 impl<T> IntoIterator for Results<T> {
@@ -37,3 +188,17 @@ impl<'i, T> IntoIterator for &'i Results<T> {
         self.results.iter()
     }
 }
+
+impl<T> AsRef<[T]> for Results<T> {
+    fn as_ref(&self) -> &[T] {
+        &self.results
+    }
+}
+
+impl<T> Index<usize> for Results<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.results[index]
+    }
+}
@@ -0,0 +1,30 @@
+use std::{future::Future, time::Duration};
+
+/// Waits for the given duration on the async runtime selected through features
+///
+/// Tokio is preferred if both runtimes are enabled.
+pub(crate) async fn sleep(duration: Duration) {
+  #[cfg(feature = "tokio-runtime")]
+  tokio::time::delay_for(duration).await;
+
+  #[cfg(all(feature = "async-std-runtime", not(feature = "tokio-runtime")))]
+  async_std::task::sleep(duration).await;
+}
+
+/// Runs a future for at most the given duration, returning `None` if it did not complete in time
+#[cfg(feature = "tokio-runtime")]
+pub(crate) async fn timeout<F>(duration: Duration, future: F) -> Option<F::Output>
+where
+  F: Future,
+{
+  tokio::time::timeout(duration, future).await.ok()
+}
+
+/// Runs a future for at most the given duration, returning `None` if it did not complete in time
+#[cfg(all(feature = "async-std-runtime", not(feature = "tokio-runtime")))]
+pub(crate) async fn timeout<F>(duration: Duration, future: F) -> Option<F::Output>
+where
+  F: Future,
+{
+  async_std::future::timeout(duration, future).await.ok()
+}
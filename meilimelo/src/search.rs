@@ -1,7 +1,12 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+use std::{borrow::Cow, collections::HashMap, fmt};
+
 use reqwest::{Method, StatusCode};
-use serde::Deserialize;
+use serde::{de::IgnoredAny, Deserialize, Serialize, Serializer};
+use serde_json::{Map, Value};
 
-use crate::{facets::Facets, results::Results, Error, MeiliMelo, Schema};
+use crate::{client::JsonBody, facets::Facets, geo::GeoPoint, results::Results, Error, MeiliMelo, Schema};
 
 /// Utility to build a search query
 ///
@@ -30,33 +35,132 @@ use crate::{facets::Facets, results::Results, Error, MeiliMelo, Schema};
 ///   .await;
 /// # }
 /// ```
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Query<'m> {
   #[serde(skip_serializing)]
   meili: &'m MeiliMelo<'m>,
 
   #[serde(skip_serializing)]
-  index: &'m str,
-  #[serde(rename = "q")]
-  query: Option<&'m str>,
-  filters: Option<&'m str>,
+  pub(crate) index: &'m str,
+  #[serde(skip_serializing)]
+  pub(crate) key: Option<&'m str>,
+  #[serde(rename = "q", skip_serializing_if = "QueryText::is_none")]
+  query: QueryText<'m>,
+  #[cfg_attr(not(feature = "legacy-filters"), serde(rename = "filter"))]
+  filters: Option<Cow<'m, str>>,
   #[serde(rename = "facetFilters")]
   facets: Option<Vec<Vec<String>>>,
   limit: Option<i64>,
   offset: Option<i64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  page: Option<i64>,
+  #[serde(rename = "hitsPerPage", skip_serializing_if = "Option::is_none")]
+  hits_per_page: Option<i64>,
+  /// Maximum value of `limit` and `hitsPerPage`, enforced when the query is run
+  #[serde(skip_serializing)]
+  max_limit: Option<i64>,
 
   #[serde(rename = "attributesToRetrieve")]
-  retrieve: Option<&'m [&'m str]>,
+  retrieve: Option<Vec<&'m str>>,
   #[serde(rename = "attributesToCrop")]
   crop: Option<Vec<String>>,
   #[serde(rename = "cropLength")]
   crop_length: Option<i64>,
+  #[serde(rename = "cropMarker", skip_serializing_if = "Option::is_none")]
+  crop_marker: Option<&'m str>,
+  /// Attribute given a negative crop length, reported when the query is run
+  #[serde(skip_serializing)]
+  invalid_crop: Option<String>,
   #[serde(rename = "attributesToHighlight")]
-  highlight: Option<&'m [&'m str]>,
+  highlight: Option<Vec<&'m str>>,
+  #[serde(rename = "highlightPreTag", skip_serializing_if = "Option::is_none")]
+  highlight_pre_tag: Option<&'m str>,
+  #[serde(rename = "highlightPostTag", skip_serializing_if = "Option::is_none")]
+  highlight_post_tag: Option<&'m str>,
   #[serde(rename = "facetsDistribution")]
-  distribution: Option<&'m [&'m str]>,
-  #[serde(rename = "matches")]
-  matches: bool,
+  distribution: Option<Vec<&'m str>>,
+  #[serde(rename = "showMatchesPosition", skip_serializing_if = "is_false")]
+  matches_position: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  distinct: Option<&'m str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  sort: Option<Vec<String>>,
+  #[serde(rename = "showRankingScore", skip_serializing_if = "Option::is_none")]
+  ranking_score: Option<bool>,
+  #[serde(rename = "showRankingScoreDetails", skip_serializing_if = "Option::is_none")]
+  ranking_score_details: Option<bool>,
+  #[serde(rename = "matchingStrategy", skip_serializing_if = "Option::is_none")]
+  matching_strategy: Option<MatchingStrategy>,
+  #[serde(rename = "rankingScoreThreshold", skip_serializing_if = "Option::is_none")]
+  ranking_score_threshold: Option<f64>,
+  #[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "tokio-runtime", feature = "async-std-runtime")
+  ))]
+  #[serde(skip_serializing)]
+  deadline: Option<Instant>,
+}
+
+/// Enum representing the text of a search query
+///
+/// MeiliSearch can behave differently depending on whether the `q` parameter
+/// is omitted, empty or `null`, this allows to pick the exact one to be sent.
+/// A `&str` or a `&String` can be converted into `QueryText::Text`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QueryText<'a> {
+  /// Omit the `q` parameter from the query
+  None,
+  /// Send `q` as `null`
+  Null,
+  /// Send `q` as an empty string
+  Empty,
+  /// Send `q` with the given text
+  Text(&'a str),
+}
+
+impl<'a> QueryText<'a> {
+  fn is_none(&self) -> bool {
+    *self == QueryText::None
+  }
+}
+
+impl<'a> From<&'a str> for QueryText<'a> {
+  fn from(text: &'a str) -> QueryText<'a> {
+    QueryText::Text(text)
+  }
+}
+
+impl<'a> From<&'a String> for QueryText<'a> {
+  fn from(text: &'a String) -> QueryText<'a> {
+    QueryText::Text(text)
+  }
+}
+
+impl<'a> Serialize for QueryText<'a> {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    match self {
+      QueryText::None | QueryText::Null => serializer.serialize_none(),
+      QueryText::Empty => serializer.serialize_str(""),
+      QueryText::Text(text) => serializer.serialize_str(text),
+    }
+  }
+}
+
+/// Strategy used to match documents when not all query terms are found
+///
+/// See the [MeiliSearch documentation](https://www.meilisearch.com/docs/reference/api/search#matching-strategy).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchingStrategy {
+  /// Drop query terms starting from the last one until documents match
+  Last,
+  /// Only return documents containing all query terms
+  All,
+  /// Drop the most frequent query terms first until documents match
+  Frequency,
 }
 
 /// Enum representing an attribute crop instruction
@@ -67,41 +171,209 @@ pub enum Crop<'a> {
   At(&'a str, i64),
 }
 
-#[derive(Debug, Deserialize)]
+/// Enum representing a sort instruction
+pub enum Sort<'a> {
+  /// Sort the hits by ascending values of the specified attribute
+  Asc(&'a str),
+  /// Sort the hits by descending values of the specified attribute
+  Desc(&'a str),
+}
+
+/// Error reported by MeiliSearch, either for a query or for a failed operation
+#[derive(Debug, Clone, Deserialize)]
 pub struct QueryError {
-  #[serde(rename = "errorType")]
+  #[serde(rename = "errorType", alias = "type")]
   pub kind: String,
-  #[serde(rename = "errorCode")]
+  #[serde(rename = "errorCode", alias = "code")]
   pub code: String,
   pub message: String,
-  #[serde(rename = "errorLink")]
+  #[serde(rename = "errorLink", alias = "link")]
   pub link: String,
 }
 
+impl fmt::Display for QueryError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} ({}, see {})", self.message, self.code, self.link)
+  }
+}
+
+impl std::error::Error for QueryError {}
+
+/// Owned search parameters, which can be deserialized from untrusted input
+///
+/// This mirrors the body of a MeiliSearch search request, and can be applied
+/// to a query through
+/// [`MeiliMelo::search_with()`](struct.MeiliMelo.html#method.search_with).
+/// Unknown attributes are rejected when deserializing.
+///
+/// # Examples
+///
+/// ```
+/// use meilimelo::SearchParams;
+///
+/// let params: SearchParams = serde_json::from_str(r#"{ "q": "skywalker", "limit": 10 }"#).unwrap();
+///
+/// assert_eq!(params.q.as_deref(), Some("skywalker"));
+/// ```
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SearchParams {
+  pub q: Option<String>,
+  pub filter: Option<String>,
+  pub facet_filters: Option<Vec<Vec<String>>>,
+  pub limit: Option<i64>,
+  pub offset: Option<i64>,
+  pub page: Option<i64>,
+  pub hits_per_page: Option<i64>,
+  pub attributes_to_retrieve: Option<Vec<String>>,
+  pub attributes_to_crop: Option<Vec<String>>,
+  pub crop_length: Option<i64>,
+  pub crop_marker: Option<String>,
+  pub attributes_to_highlight: Option<Vec<String>>,
+  pub highlight_pre_tag: Option<String>,
+  pub highlight_post_tag: Option<String>,
+  #[serde(alias = "facetsDistribution")]
+  pub facets: Option<Vec<String>>,
+  pub distinct: Option<String>,
+  pub sort: Option<Vec<String>>,
+  pub show_matches_position: Option<bool>,
+  pub show_ranking_score: Option<bool>,
+  pub show_ranking_score_details: Option<bool>,
+  pub matching_strategy: Option<MatchingStrategy>,
+  pub ranking_score_threshold: Option<f64>,
+}
+
+fn as_strs(values: &Option<Vec<String>>) -> Option<Vec<&str>> {
+  values
+    .as_ref()
+    .map(|values| values.iter().map(String::as_str).collect())
+}
+
+fn is_false(value: &bool) -> bool {
+  !value
+}
+
 impl<'m> Query<'m> {
   pub(crate) fn new(meili: &'m MeiliMelo, index: &'m str) -> Query<'m> {
     Query {
       meili,
       index,
-      query: None,
+      key: None,
+      query: QueryText::None,
       filters: None,
       facets: None,
       limit: None,
       offset: None,
+      page: None,
+      hits_per_page: None,
+      max_limit: None,
       retrieve: None,
       crop: None,
       crop_length: None,
+      crop_marker: None,
+      invalid_crop: None,
       highlight: None,
+      highlight_pre_tag: None,
+      highlight_post_tag: None,
       distribution: None,
-      matches: false,
+      matches_position: false,
+      distinct: None,
+      sort: None,
+      ranking_score: None,
+      ranking_score_details: None,
+      matching_strategy: None,
+      ranking_score_threshold: None,
+      #[cfg(all(
+        not(target_arch = "wasm32"),
+        any(feature = "tokio-runtime", feature = "async-std-runtime")
+      ))]
+      deadline: None,
+    }
+  }
+
+  /// Overrides the parameters of the query with those that are set in `params`
+  pub(crate) fn apply(mut self, params: &'m SearchParams) -> Query<'m> {
+    if let Some(ref query) = params.q {
+      self.query = QueryText::Text(query);
     }
+
+    self.filters = params.filter.as_deref().map(Cow::Borrowed).or(self.filters);
+    self.facets = params.facet_filters.clone().or(self.facets);
+    self.limit = params.limit.or(self.limit);
+    self.offset = params.offset.or(self.offset);
+    self.page = params.page.or(self.page);
+    self.hits_per_page = params.hits_per_page.or(self.hits_per_page);
+    self.retrieve = as_strs(&params.attributes_to_retrieve).or(self.retrieve);
+    self.crop = params.attributes_to_crop.clone().or(self.crop);
+    self.crop_length = params.crop_length.or(self.crop_length);
+    self.crop_marker = params.crop_marker.as_deref().or(self.crop_marker);
+    self.highlight = as_strs(&params.attributes_to_highlight).or(self.highlight);
+    self.highlight_pre_tag = params.highlight_pre_tag.as_deref().or(self.highlight_pre_tag);
+    self.highlight_post_tag = params.highlight_post_tag.as_deref().or(self.highlight_post_tag);
+    self.distribution = as_strs(&params.facets).or(self.distribution);
+    self.distinct = params.distinct.as_deref().or(self.distinct);
+    self.sort = params.sort.clone().or(self.sort);
+    self.matches_position = params.show_matches_position.unwrap_or(self.matches_position);
+    self.ranking_score = params.show_ranking_score.or(self.ranking_score);
+    self.ranking_score_details = params.show_ranking_score_details.or(self.ranking_score_details);
+    self.matching_strategy = params.matching_strategy.or(self.matching_strategy);
+    self.ranking_score_threshold = params.ranking_score_threshold.or(self.ranking_score_threshold);
+    self
+  }
+
+  /// Use another secret key for this query only
+  ///
+  /// This overrides the secret key of the [`MeiliMelo`](../struct.MeiliMelo.html)
+  /// descriptor, for example to search with a per-user tenant token.
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - secret key or tenant token to use
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// MeiliMelo::new("host").search("index").with_key("tenant-token");
+  /// ```
+  pub fn with_key(mut self, key: &'m str) -> Query<'m> {
+    self.key = Some(key);
+    self
+  }
+
+  /// Run the query against another index
+  ///
+  /// Combined with `Clone`, this allows to run the same query against
+  /// several indices without building it again.
+  ///
+  /// # Arguments
+  ///
+  /// * `index` - name of the index to search
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// let meili = MeiliMelo::new("host");
+  /// let query = meili.search("tenant-a").query("skywalker").limit(10);
+  ///
+  /// let queries: Vec<Query> = vec!["tenant-b", "tenant-c"]
+  ///   .into_iter()
+  ///   .map(|tenant| query.clone().index(tenant))
+  ///   .collect();
+  /// ```
+  pub fn index(mut self, index: &'m str) -> Query<'m> {
+    self.index = index;
+    self
   }
 
   /// [MeiliSearch documentation](https://docs.meilisearch.com/guides/advanced_guides/search_parameters.html#query-q)
   ///
   /// # Arguments
   ///
-  /// * `query` - string of the query to be used
+  /// * `query` - string of the query to be used, or a [`QueryText`](enum.QueryText.html) to control how it is sent
   ///
   /// # Examples
   ///
@@ -109,17 +381,24 @@ impl<'m> Query<'m> {
   /// # use meilimelo::prelude::*;
   /// #
   /// MeiliMelo::new("host").search("index").query("streamer");
+  /// MeiliMelo::new("host").search("index").query(QueryText::Empty);
   /// ```
-  pub fn query(mut self, query: &'m str) -> Query {
-    self.query = Some(query);
+  pub fn query<Q>(mut self, query: Q) -> Query<'m>
+  where
+    Q: Into<QueryText<'m>>,
+  {
+    self.query = query.into();
     self
   }
 
   /// [MeiliSearch documentation](https://docs.meilisearch.com/guides/advanced_guides/search_parameters.html#filters)
   ///
+  /// This is sent as the `filter` parameter, or as `filters` for older
+  /// MeiliSearch instances when the `legacy-filters` feature is enabled.
+  ///
   /// # Arguments
   ///
-  /// * `filters` - string representing to filter to be applied
+  /// * `filters` - string representing to filter to be applied, either borrowed or owned
   ///
   /// # Examples
   ///
@@ -128,8 +407,75 @@ impl<'m> Query<'m> {
   /// #
   /// MeiliMelo::new("host").search("index").filters("company = ACME AND age > 23");
   /// ```
-  pub fn filters(mut self, filters: &'m str) -> Query {
-    self.filters = Some(filters);
+  pub fn filters<F>(mut self, filters: F) -> Query<'m>
+  where
+    F: Into<Cow<'m, str>>,
+  {
+    self.filters = Some(filters.into());
+    self
+  }
+
+  /// [MeiliSearch documentation](https://www.meilisearch.com/docs/learn/filtering_and_sorting/geosearch#filtering-results-with-_georadius-and-_geoboundingbox)
+  ///
+  /// Only keeps documents whose `_geo` attribute is within the given
+  /// distance of a point. The expression is combined with the filters already
+  /// set on the query using `AND`, while setting
+  /// [`filters`](#method.filters) afterwards replaces it.
+  ///
+  /// # Arguments
+  ///
+  /// * `lat` - latitude of the center of the area, in degrees
+  /// * `lng` - longitude of the center of the area, in degrees
+  /// * `meters` - radius of the area, in meters
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// MeiliMelo::new("host").search("index")
+  ///   .filters("type = restaurant")
+  ///   .geo_radius(48.8566, 2.3522, 2000.0);
+  /// ```
+  pub fn geo_radius(self, lat: f64, lng: f64, meters: f64) -> Query<'m> {
+    self.and_filter(format!("_geoRadius({}, {}, {})", lat, lng, meters))
+  }
+
+  /// [MeiliSearch documentation](https://www.meilisearch.com/docs/learn/filtering_and_sorting/geosearch#filtering-results-with-_georadius-and-_geoboundingbox)
+  ///
+  /// Only keeps documents whose `_geo` attribute is within the given
+  /// rectangle. The expression is combined with the filters already set on
+  /// the query using `AND`, while setting [`filters`](#method.filters)
+  /// afterwards replaces it.
+  ///
+  /// # Arguments
+  ///
+  /// * `top_right` - north-eastern corner of the area
+  /// * `bottom_left` - south-western corner of the area
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::{prelude::*, GeoPoint};
+  /// #
+  /// MeiliMelo::new("host").search("index")
+  ///   .geo_bounding_box(GeoPoint::new(48.9, 2.4), GeoPoint::new(48.8, 2.3));
+  /// ```
+  pub fn geo_bounding_box(self, top_right: GeoPoint, bottom_left: GeoPoint) -> Query<'m> {
+    self.and_filter(format!(
+      "_geoBoundingBox([{}, {}], [{}, {}])",
+      top_right.lat, top_right.lng, bottom_left.lat, bottom_left.lng
+    ))
+  }
+
+  /// Combines an expression with the filters already set on the query
+  fn and_filter(mut self, expression: String) -> Query<'m> {
+    let filters = match self.filters {
+      Some(filters) => format!("({}) AND {}", filters, expression),
+      None => expression,
+    };
+
+    self.filters = Some(Cow::Owned(filters));
     self
   }
 
@@ -169,10 +515,79 @@ impl<'m> Query<'m> {
     self
   }
 
+  /// [MeiliSearch documentation](https://www.meilisearch.com/docs/reference/api/search#page)
+  ///
+  /// Page-based pagination cannot be combined with `limit` and `offset`, and
+  /// running such a query returns an error.
+  ///
+  /// # Arguments
+  ///
+  /// * `page` - number of the page to return, starting at 1
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// MeiliMelo::new("host").search("index").page(2);
+  /// ```
+  pub fn page(mut self, page: i64) -> Query<'m> {
+    self.page = Some(page);
+    self
+  }
+
+  /// [MeiliSearch documentation](https://www.meilisearch.com/docs/reference/api/search#number-of-results-per-page)
+  ///
+  /// Page-based pagination cannot be combined with `limit` and `offset`, and
+  /// running such a query returns an error.
+  ///
+  /// # Arguments
+  ///
+  /// * `hits` - maximum number of hits in a page
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// MeiliMelo::new("host").search("index").hits_per_page(20);
+  /// ```
+  pub fn hits_per_page(mut self, hits: i64) -> Query<'m> {
+    self.hits_per_page = Some(hits);
+    self
+  }
+
+  /// Cap the number of hits that can be requested by the query
+  ///
+  /// When the query is run, `limit` and `hits_per_page` are lowered to `max`
+  /// if they exceed it, whatever the order in which they were set. This
+  /// allows to forward parameters supplied by a third party without letting
+  /// it request an arbitrary number of hits. If neither is set, the default
+  /// of the instance applies.
+  ///
+  /// # Arguments
+  ///
+  /// * `max` - maximum number of hits that can be requested
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// MeiliMelo::new("host").search("index").limit(10000).max_limit(100);
+  /// ```
+  pub fn max_limit(mut self, max: i64) -> Query<'m> {
+    self.max_limit = Some(max);
+    self
+  }
+
   /// [MeiliSearch documentation](https://docs.meilisearch.com/guides/advanced_guides/search_parameters.html#facetFilters)
   ///
   /// [`FacetBuilder`](facets/struct.FacetBuilder.html) must be used to create the facet statement.
   ///
+  /// This method is additive: calling it several times combines the groups of
+  /// all the given facets with `AND`, instead of replacing the previous ones.
+  ///
   /// # Arguments
   ///
   /// * `facets` - facets to apply to the search
@@ -189,7 +604,69 @@ impl<'m> Query<'m> {
   ///   .build());
   /// ```
   pub fn facets(mut self, facets: Facets) -> Query<'m> {
-    self.facets = Some(facets.get());
+    self.facets.get_or_insert_with(Vec::new).extend(facets.get());
+    self
+  }
+
+  /// Remove the filters previously set on the query
+  ///
+  /// This is useful to derive a new query from a cloned base query.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// let meili = MeiliMelo::new("host");
+  /// let base = meili.search("index").filters("age > 18");
+  /// let unfiltered = base.clone().clear_filters();
+  /// ```
+  pub fn clear_filters(mut self) -> Query<'m> {
+    self.filters = None;
+    self
+  }
+
+  /// Remove all the facets previously added to the query
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// MeiliMelo::new("host").search("index")
+  ///   .facets(FacetBuilder::new("company", "ACME Corp").build())
+  ///   .clear_facets();
+  /// ```
+  pub fn clear_facets(mut self) -> Query<'m> {
+    self.facets = None;
+    self
+  }
+
+  /// Remove the limit previously set on the query, falling back to MeiliSearch's default
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// MeiliMelo::new("host").search("index").limit(10).clear_limit();
+  /// ```
+  pub fn clear_limit(mut self) -> Query<'m> {
+    self.limit = None;
+    self
+  }
+
+  /// Remove the offset previously set on the query
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// MeiliMelo::new("host").search("index").offset(20).clear_offset();
+  /// ```
+  pub fn clear_offset(mut self) -> Query<'m> {
+    self.offset = None;
     self
   }
 
@@ -207,7 +684,7 @@ impl<'m> Query<'m> {
   /// MeiliMelo::new("host").search("index").retrieve(&["firstname", "lastname"]);
   /// ```
   pub fn retrieve(mut self, attributes: &'m [&'m str]) -> Query<'m> {
-    self.retrieve = Some(attributes);
+    self.retrieve = Some(attributes.to_vec());
     self
   }
 
@@ -225,7 +702,7 @@ impl<'m> Query<'m> {
   /// MeiliMelo::new("host").search("index").distribution(&["firstname", "lastname"]);
   /// ```
   pub fn distribution(mut self, facets: &'m [&'m str]) -> Query<'m> {
-    self.distribution = Some(facets);
+    self.distribution = Some(facets.to_vec());
     self
   }
 
@@ -246,7 +723,15 @@ impl<'m> Query<'m> {
   ///      Crop::At("description", 10)
   ///    ]);
   /// ```
+  ///
+  /// Crop lengths must not be negative, otherwise running the query returns
+  /// an error without sending it.
   pub fn crop(mut self, attributes: &'m [Crop]) -> Query<'m> {
+    self.invalid_crop = attributes.iter().find_map(|spec| match spec {
+      Crop::At(attribute, length) if *length < 0 => Some(attribute.to_string()),
+      _ => None,
+    });
+
     let crops = attributes
       .iter()
       .map(|spec| match spec {
@@ -277,6 +762,27 @@ impl<'m> Query<'m> {
     self
   }
 
+  /// [MeiliSearch documentation](https://www.meilisearch.com/docs/reference/api/search#crop-marker)
+  ///
+  /// By default, cropped attributes are marked with `…`. An empty marker can
+  /// be used to omit it.
+  ///
+  /// # Arguments
+  ///
+  /// * `marker` - string inserted where attributes were cropped
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// MeiliMelo::new("host").search("index").crop_marker(" [...]");
+  /// ```
+  pub fn crop_marker(mut self, marker: &'m str) -> Query<'m> {
+    self.crop_marker = Some(marker);
+    self
+  }
+
   /// [MeiliSearch documentation](https://docs.meilisearch.com/guides/advanced_guides/search_parameters.html#attributesToRetrieve)
   ///
   /// # Arguments
@@ -291,55 +797,1012 @@ impl<'m> Query<'m> {
   /// MeiliMelo::new("host").search("index").highlight(&["overview"]);
   /// ```
   pub fn highlight(mut self, attributes: &'m [&'m str]) -> Query<'m> {
-    self.highlight = Some(attributes);
+    self.highlight = Some(attributes.to_vec());
     self
   }
 
-  pub async fn run<R>(self) -> Result<Results<R>, Error>
-  where
-    R: Schema + for<'de> Deserialize<'de>,
-  {
-    let response = self
-      .meili
-      .request(Method::POST, &format!("/indexes/{}/search", self.index))
-      .json(&self)
-      .send()
-      .await
-      .map_err(|err| Error::UpstreamError(err))?;
-
-    match response.status() {
-      StatusCode::OK => {
-        let response = response
-          .json::<Results<R>>()
-          .await
-          .map_err(|err| Error::UpstreamError(err))?;
-
-        Ok(response)
-      }
-
-      _ => {
-        let error = response
-          .json::<QueryError>()
-          .await
-          .map_err(|err| Error::UpstreamError(err))?;
-
-        Err(Error::InvalidQuery(error))
-      }
-    }
+  /// [MeiliSearch documentation](https://www.meilisearch.com/docs/reference/api/search#highlight-tags)
+  ///
+  /// By default, highlighted terms are wrapped in `<em>` and `</em>`.
+  ///
+  /// # Arguments
+  ///
+  /// * `pre` - string inserted before highlighted terms
+  /// * `post` - string inserted after highlighted terms
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// MeiliMelo::new("host").search("index")
+  ///   .highlight(&["overview"])
+  ///   .highlight_tags("<mark class=\"hl\">", "</mark>");
+  /// ```
+  pub fn highlight_tags(mut self, pre: &'m str, post: &'m str) -> Query<'m> {
+    self.highlight_pre_tag = Some(pre);
+    self.highlight_post_tag = Some(post);
+    self
   }
-}
 
-#[cfg(test)]
-mod tests {
-  use crate::prelude::*;
+  /// [MeiliSearch documentation](https://www.meilisearch.com/docs/reference/api/search#distinct-attributes-at-search-time)
+  ///
+  /// This overrides the distinct attribute set in the index settings for
+  /// this query only. It is unrelated to facet
+  /// [`distribution`](#method.distribution).
+  ///
+  /// # Arguments
+  ///
+  /// * `attribute` - attribute used to deduplicate hits
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// MeiliMelo::new("host").search("index").distinct("sku");
+  /// ```
+  pub fn distinct(mut self, attribute: &'m str) -> Query<'m> {
+    self.distinct = Some(attribute);
+    self
+  }
 
-  #[test]
+  /// [MeiliSearch documentation](https://www.meilisearch.com/docs/reference/api/search#sort)
+  ///
+  /// Sorted attributes must be added to the sortable attributes of the
+  /// index. Hits are sorted by the first attribute, then by the following
+  /// ones for equal values. Calling this method several times, or combining
+  /// it with [`geo_sort`](#method.geo_sort), appends to the sort instructions
+  /// already set on the query.
+  ///
+  /// # Arguments
+  ///
+  /// * `attributes` - slice of attributes to sort the hits by, with their order
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// MeiliMelo::new("host").search("index")
+  ///   .sort(&[
+  ///      Sort::Asc("price"),
+  ///      Sort::Desc("releaseDate")
+  ///    ]);
+  /// ```
+  pub fn sort(mut self, attributes: &[Sort]) -> Query<'m> {
+    let sort = attributes.iter().map(|spec| match spec {
+      Sort::Asc(attribute) => format!("{}:asc", attribute),
+      Sort::Desc(attribute) => format!("{}:desc", attribute),
+    });
+
+    self.sort.get_or_insert_with(Vec::new).extend(sort);
+    self
+  }
+
+  /// [MeiliSearch documentation](https://www.meilisearch.com/docs/learn/filtering_and_sorting/geosearch#sorting-results-with-_geopoint)
+  ///
+  /// Sorts the hits by the distance between their `_geo` attribute and the
+  /// given point. The instruction is appended to those already set on the
+  /// query, so it can be combined with [`sort`](#method.sort) to break ties.
+  ///
+  /// # Arguments
+  ///
+  /// * `lat` - latitude of the point, in degrees
+  /// * `lng` - longitude of the point, in degrees
+  /// * `ascending` - whether to return the closest hits first
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// MeiliMelo::new("host").search("index")
+  ///   .geo_sort(48.8566, 2.3522, true)
+  ///   .sort(&[Sort::Desc("rating")]);
+  /// ```
+  pub fn geo_sort(mut self, lat: f64, lng: f64, ascending: bool) -> Query<'m> {
+    let order = if ascending { "asc" } else { "desc" };
+
+    self
+      .sort
+      .get_or_insert_with(Vec::new)
+      .push(format!("_geoPoint({}, {}):{}", lat, lng, order));
+
+    self
+  }
+
+  /// [MeiliSearch documentation](https://www.meilisearch.com/docs/reference/api/search#show-matches-position)
+  ///
+  /// When enabled, each hit contains a `_matchesPosition` attribute, giving
+  /// the location of the matched terms in each attribute.
+  ///
+  /// # Arguments
+  ///
+  /// * `show` - whether to return the position of the matched terms
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// MeiliMelo::new("host").search("index").show_matches_position(true);
+  /// ```
+  pub fn show_matches_position(mut self, show: bool) -> Query<'m> {
+    self.matches_position = show;
+    self
+  }
+
+  /// [MeiliSearch documentation](https://www.meilisearch.com/docs/reference/api/search#ranking-score)
+  ///
+  /// When enabled, each hit contains a `_rankingScore` attribute, between 0
+  /// and 1, which can be added to a schema as an `f64`.
+  ///
+  /// # Arguments
+  ///
+  /// * `show` - whether to return the ranking score of each hit
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// MeiliMelo::new("host").search("index").show_ranking_score(true);
+  /// ```
+  pub fn show_ranking_score(mut self, show: bool) -> Query<'m> {
+    self.ranking_score = Some(show);
+    self
+  }
+
+  /// [MeiliSearch documentation](https://www.meilisearch.com/docs/reference/api/search#ranking-score-details)
+  ///
+  /// When enabled, each hit contains a `_rankingScoreDetails` attribute
+  /// detailing the score computed by each ranking rule, which can be read
+  /// through [`RankingScoreDetails`](../type.RankingScoreDetails.html).
+  ///
+  /// # Arguments
+  ///
+  /// * `show` - whether to return the ranking score details of each hit
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// MeiliMelo::new("host").search("index").show_ranking_score_details(true);
+  /// ```
+  pub fn show_ranking_score_details(mut self, show: bool) -> Query<'m> {
+    self.ranking_score_details = Some(show);
+    self
+  }
+
+  /// [MeiliSearch documentation](https://www.meilisearch.com/docs/reference/api/search#matching-strategy)
+  ///
+  /// Ranking rules themselves, including `typo` and `sort`, can only be
+  /// configured index-wide through the settings, and cannot be disabled for
+  /// a single query. The matching strategy and the
+  /// [ranking score threshold](#method.ranking_score_threshold) are the only
+  /// per-query adjustments to how documents are matched and ranked.
+  ///
+  /// # Arguments
+  ///
+  /// * `strategy` - how to match documents when not all query terms are found
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::{prelude::*, MatchingStrategy};
+  /// #
+  /// MeiliMelo::new("host").search("index").matching_strategy(MatchingStrategy::All);
+  /// ```
+  pub fn matching_strategy(mut self, strategy: MatchingStrategy) -> Query<'m> {
+    self.matching_strategy = Some(strategy);
+    self
+  }
+
+  /// [MeiliSearch documentation](https://www.meilisearch.com/docs/reference/api/search#ranking-score-threshold)
+  ///
+  /// Hits with a ranking score below the threshold are excluded from the
+  /// results. The threshold must be between 0 and 1, otherwise running the
+  /// query returns an error without sending it.
+  ///
+  /// MeiliSearch does not report how many hits were excluded by the
+  /// threshold: the total number of hits of the results only counts the
+  /// remaining ones. To tune the threshold, the same query can be run
+  /// without it and its total number of hits compared, while
+  /// [`show_ranking_score()`](#method.show_ranking_score) shows how close
+  /// the returned hits are to the threshold.
+  ///
+  /// # Arguments
+  ///
+  /// * `threshold` - minimum ranking score of the returned hits
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// MeiliMelo::new("host").search("index").ranking_score_threshold(0.5);
+  /// ```
+  pub fn ranking_score_threshold(mut self, threshold: f64) -> Query<'m> {
+    self.ranking_score_threshold = Some(threshold);
+    self
+  }
+
+  /// Set an instant after which the query is abandoned
+  ///
+  /// The deadline caps the total time spent running the query, and the
+  /// query fails with [`Error::DeadlineExceeded`](../enum.Error.html#variant.DeadlineExceeded)
+  /// if it is reached, even if a response is being received. Unlike the
+  /// client-wide timeouts, which still fail with
+  /// [`Error::UpstreamError`](../enum.Error.html#variant.UpstreamError), it
+  /// can be shared by several queries to enforce an overall budget.
+  ///
+  /// This requires either the `tokio-runtime` (enabled by default) or the
+  /// `async-std-runtime` feature.
+  ///
+  /// # Arguments
+  ///
+  /// * `deadline` - instant after which the query should be abandoned
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// # use std::time::{Duration, Instant};
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// MeiliMelo::new("host").search("index").deadline(Instant::now() + Duration::from_secs(2));
+  /// ```
+  #[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "tokio-runtime", feature = "async-std-runtime")
+  ))]
+  pub fn deadline(mut self, deadline: Instant) -> Query<'m> {
+    self.deadline = Some(deadline);
+    self
+  }
+
+  pub async fn run<R>(self) -> Result<Results<R>, Error>
+  where
+    R: Schema + for<'de> Deserialize<'de>,
+  {
+    self.execute::<R>().await
+  }
+
+  /// Run the query as a `GET` request, with its parameters in the query string
+  ///
+  /// This allows caches that ignore `POST` requests to store the results.
+  /// Parameters are encoded as follows:
+  ///
+  /// * strings, numbers and booleans are sent as is,
+  /// * lists of strings, such as `attributesToRetrieve` or `sort`, are joined
+  ///   with commas,
+  /// * nested lists, such as `facetFilters`, are sent as JSON,
+  /// * `q` cannot be sent as `null`, so [`QueryText::Null`](enum.QueryText.html#variant.Null)
+  ///   is treated as [`QueryText::None`](enum.QueryText.html#variant.None).
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[meilimelo::schema]
+  /// # struct Employee { firstname: String };
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let results = MeiliMelo::new("host")
+  ///   .search("employees")
+  ///   .query("skywalker")
+  ///   .retrieve(&["firstname", "lastname"])
+  ///   .run_get::<Employee>()
+  ///   .await;
+  /// # }
+  /// ```
+  pub async fn run_get<R>(self) -> Result<Results<R>, Error>
+  where
+    R: Schema + for<'de> Deserialize<'de>,
+  {
+    self.execute_as::<R>(Method::GET).await
+  }
+
+  /// Run the query and only return the hits, without the results metadata
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[meilimelo::schema]
+  /// # struct Employee { firstname: String, lastname: String };
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let employees: Vec<Employee> = MeiliMelo::new("host")
+  ///   .search("employees")
+  ///   .query("skywalker")
+  ///   .run_hits()
+  ///   .await
+  ///   .unwrap();
+  /// # }
+  /// ```
+  pub async fn run_hits<R>(self) -> Result<Vec<R>, Error>
+  where
+    R: Schema + for<'de> Deserialize<'de>,
+  {
+    Ok(self.execute::<R>().await?.results)
+  }
+
+  /// Run the query and deserialize the hits into any type
+  ///
+  /// Unlike [`Query::run()`](#method.run), `R` does not need to be a
+  /// [`Schema`](../trait.Schema.html), so several plain structs can be used
+  /// to shape the hits of the same index, for example a list view and a
+  /// detail view. The `_formatted` attribute is only read if `R` declares it.
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// #[derive(serde::Deserialize)]
+  /// struct EmployeeSummary {
+  ///   firstname: String,
+  /// }
+  ///
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let results = MeiliMelo::new("host")
+  ///   .search("employees")
+  ///   .query("skywalker")
+  ///   .retrieve(&["firstname"])
+  ///   .run_as::<EmployeeSummary>()
+  ///   .await;
+  /// # }
+  /// ```
+  pub async fn run_as<R>(self) -> Result<Results<R>, Error>
+  where
+    for<'de> R: Deserialize<'de>,
+  {
+    self.execute::<R>().await
+  }
+
+  /// Run the query and only keep the hits matching the given predicate
+  ///
+  /// Filtering happens client-side, after MeiliSearch returned a page of
+  /// results: the page may therefore contain fewer hits than the requested
+  /// limit, and pagination is still computed by MeiliSearch on unfiltered
+  /// hits. The total number of hits is decreased by the number of dropped
+  /// hits, so it only stays accurate when all hits fit in one page.
+  ///
+  /// # Arguments
+  ///
+  /// * `predicate` - function returning whether a hit should be kept
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[meilimelo::schema]
+  /// # struct Employee { firstname: String, lastname: String };
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let results = MeiliMelo::new("host")
+  ///   .search("employees")
+  ///   .query("skywalker")
+  ///   .run_filtered::<Employee, _>(|employee| employee.firstname != employee.lastname)
+  ///   .await;
+  /// # }
+  /// ```
+  pub async fn run_filtered<R, F>(self, predicate: F) -> Result<Results<R>, Error>
+  where
+    R: Schema + for<'de> Deserialize<'de>,
+    F: Fn(&R) -> bool,
+  {
+    let mut results = self.execute::<R>().await?;
+
+    results.retain(predicate);
+
+    Ok(results)
+  }
+
+  /// Run the query and only return the value of the given attribute for each hit
+  ///
+  /// Only the given attribute is retrieved from MeiliSearch, which avoids
+  /// transferring and deserializing whole documents when only their
+  /// identifiers are needed. Hits missing the attribute are skipped.
+  ///
+  /// # Arguments
+  ///
+  /// * `id_field` - name of the attribute to extract, usually the primary key
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let ids = MeiliMelo::new("host")
+  ///   .search("employees")
+  ///   .query("skywalker")
+  ///   .ids("id")
+  ///   .await;
+  /// # }
+  /// ```
+  pub async fn ids(mut self, id_field: &'m str) -> Result<Vec<Value>, Error> {
+    self.retrieve = Some(vec![id_field]);
+
+    let results = self.execute::<Map<String, Value>>().await?;
+
+    Ok(results.into_iter().filter_map(|mut hit| hit.remove(id_field)).collect())
+  }
+
+  /// Run the query and return the raw formatted version of each hit alongside the results
+  ///
+  /// The `_formatted` object of each hit is extracted before deserializing
+  /// it, so `R` does not need to be a [`Schema`](../trait.Schema.html). The
+  /// returned `Vec` holds the formatted objects in the same order as the
+  /// hits, with `Value::Null` for hits that do not have one.
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[derive(serde::Deserialize)]
+  /// # struct Employee { firstname: String, bio: String };
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let (results, formatted) = MeiliMelo::new("host")
+  ///   .search("employees")
+  ///   .query("jedi")
+  ///   .highlight(&["bio"])
+  ///   .run_with_formatted::<Employee>()
+  ///   .await
+  ///   .unwrap();
+  ///
+  /// for (employee, formatted) in results.into_iter().zip(formatted) {
+  ///   println!("{}: {}", employee.firstname, formatted["bio"]);
+  /// }
+  /// # }
+  /// ```
+  pub async fn run_with_formatted<R>(self) -> Result<(Results<R>, Vec<Value>), Error>
+  where
+    for<'de> R: Deserialize<'de>,
+  {
+    let mut results = self.execute::<Map<String, Value>>().await?;
+    let mut hits = Vec::with_capacity(results.len());
+    let mut formatted = Vec::with_capacity(results.len());
+
+    for mut hit in results.results.drain(..) {
+      formatted.push(hit.remove("_formatted").unwrap_or(Value::Null));
+      hits.push(serde_json::from_value::<R>(Value::Object(hit)).map_err(|err| Error::ParseError(Box::new(err)))?);
+    }
+
+    Ok((results.with_results(hits), formatted))
+  }
+
+  /// Run the query for the distribution of the given facets only, without retrieving any hit
+  ///
+  /// The counts of each value of each facet are returned, keyed by facet
+  /// name. Filters and the query text are taken into account as usual.
+  ///
+  /// # Arguments
+  ///
+  /// * `facets` - names of the faceted attributes
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let distribution = MeiliMelo::new("host")
+  ///   .search("employees")
+  ///   .filters("company = Rebels")
+  ///   .facets_only(&["company", "role"])
+  ///   .await
+  ///   .unwrap();
+  ///
+  /// for (role, count) in &distribution["role"] {
+  ///   println!("{}: {}", role, count);
+  /// }
+  /// # }
+  /// ```
+  pub async fn facets_only(mut self, facets: &'m [&'m str]) -> Result<HashMap<String, HashMap<String, i64>>, Error> {
+    self.distribution = Some(facets.to_vec());
+    self.zero_hits();
+
+    Ok(self.execute::<IgnoredAny>().await?.distribution.unwrap_or_default())
+  }
+
+  /// Requests no hit, using the pagination mode already set on the query
+  fn zero_hits(&mut self) {
+    if self.page.is_some() || self.hits_per_page.is_some() {
+      self.hits_per_page = Some(0);
+    } else {
+      self.limit = Some(0);
+    }
+  }
+
+  /// Returns the same query, to be run against another index
+  pub(crate) fn on_index<'a>(self, index: &'a str) -> Query<'a>
+  where
+    'm: 'a,
+  {
+    let mut query: Query<'a> = self;
+
+    query.index = index;
+    query
+  }
+
+  /// Checks the query for contradictory parameters before it is sent
+  pub(crate) fn validate(&self) -> Result<(), Error> {
+    let offset_based = self.limit.is_some() || self.offset.is_some();
+    let page_based = self.page.is_some() || self.hits_per_page.is_some();
+
+    if offset_based && page_based {
+      return Err(Error::ValidationError(
+        "limit and offset cannot be combined with page and hits_per_page".to_string(),
+      ));
+    }
+
+    if let Some(ref attribute) = self.invalid_crop {
+      return Err(Error::ValidationError(format!(
+        "crop length of attribute {} cannot be negative",
+        attribute
+      )));
+    }
+
+    if matches!(self.crop_length, Some(length) if length < 0) {
+      return Err(Error::ValidationError("crop length cannot be negative".to_string()));
+    }
+
+    if matches!(self.ranking_score_threshold, Some(threshold) if !(0.0..=1.0).contains(&threshold)) {
+      return Err(Error::ValidationError(
+        "ranking score threshold must be between 0 and 1".to_string(),
+      ));
+    }
+
+    Ok(())
+  }
+
+  /// Lowers `limit` and `hits_per_page` to the maximum set on the query, if any
+  pub(crate) fn clamp_limits(&mut self) {
+    if let Some(max) = self.max_limit {
+      self.limit = self.limit.map(|limit| limit.min(max));
+      self.hits_per_page = self.hits_per_page.map(|hits| hits.min(max));
+    }
+  }
+
+  pub(crate) async fn execute<R>(&self) -> Result<Results<R>, Error>
+  where
+    for<'de> R: Deserialize<'de>,
+  {
+    self.execute_as::<R>(Method::POST).await
+  }
+
+  async fn execute_as<R>(&self, method: Method) -> Result<Results<R>, Error>
+  where
+    for<'de> R: Deserialize<'de>,
+  {
+    self.validate()?;
+
+    let mut body = self.clone();
+
+    body.clamp_limits();
+
+    let request = self.meili.request_with_key(
+      method.clone(),
+      &format!("/indexes/{}/search", self.index),
+      self.key.or(self.meili.secret_key.as_deref()),
+    );
+
+    let request = match method {
+      Method::GET => {
+        let body = serde_json::to_value(&body).map_err(|err| Error::SerializeError(Box::new(err)))?;
+
+        request.query(&query_string(&body))
+      }
+
+      _ => request.json_body(self.meili, &body),
+    };
+
+    // The deadline covers both sending the request and reading the response
+    let search = async {
+      #[cfg(not(target_arch = "wasm32"))]
+      let start = Instant::now();
+
+      let response = request.send().await.map_err(|err| Error::UpstreamError(err))?;
+
+      match response.status() {
+        #[cfg(not(target_arch = "wasm32"))]
+        StatusCode::OK if self.meili.config.timing => {
+          let mut results = self.meili.json::<Results<R>>(response).await?;
+
+          results.client_duration = Some(start.elapsed());
+
+          Ok(results)
+        }
+
+        StatusCode::OK => self.meili.json::<Results<R>>(response).await,
+
+        _ => {
+          let error = self.meili.json::<QueryError>(response).await?;
+
+          Err(Error::InvalidQuery(error))
+        }
+      }
+    };
+
+    #[cfg(all(
+      not(target_arch = "wasm32"),
+      any(feature = "tokio-runtime", feature = "async-std-runtime")
+    ))]
+    {
+      if let Some(deadline) = self.deadline {
+        return with_deadline(deadline, search).await;
+      }
+    }
+
+    search.await
+  }
+}
+
+/// Runs a future until the given instant, failing with `DeadlineExceeded` if it is reached
+#[cfg(all(
+  not(target_arch = "wasm32"),
+  any(feature = "tokio-runtime", feature = "async-std-runtime")
+))]
+async fn with_deadline<F, T>(deadline: Instant, future: F) -> Result<T, Error>
+where
+  F: std::future::Future<Output = Result<T, Error>>,
+{
+  let now = Instant::now();
+
+  if now >= deadline {
+    return Err(Error::DeadlineExceeded);
+  }
+
+  crate::runtime::timeout(deadline - now, future)
+    .await
+    .unwrap_or(Err(Error::DeadlineExceeded))
+}
+
+/// Encodes the parameters of a search body as query string parameters
+fn query_string(body: &Value) -> Vec<(String, String)> {
+  let parameters = match body {
+    Value::Object(parameters) => parameters,
+    _ => return vec![],
+  };
+
+  parameters
+    .iter()
+    .filter_map(|(name, value)| {
+      let value = match value {
+        Value::Null => return None,
+        Value::String(value) => value.clone(),
+        Value::Array(values) if values.iter().all(Value::is_string) => {
+          values.iter().filter_map(Value::as_str).collect::<Vec<_>>().join(",")
+        }
+        // Numbers and booleans are sent as is, and nested lists as JSON
+        value => value.to_string(),
+      };
+
+      Some((name.clone(), value))
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{query_string, MatchingStrategy, QueryError};
+  use crate::{prelude::*, Error, GeoPoint, SearchParams};
+
+  #[test]
   fn index() {
     let meili = MeiliMelo::new("");
     let query = meili.search("employees");
 
     assert_eq!(query.index, "employees");
-    assert_eq!(query.query, None);
+    assert_eq!(query.query, QueryText::None);
+
+    let query = query.query("skywalker").index("companies");
+
+    assert_eq!(query.index, "companies");
+    assert_eq!(query.query, QueryText::Text("skywalker"));
+  }
+
+  #[test]
+  fn search_with() {
+    let meili = MeiliMelo::new("");
+    let params: SearchParams = serde_json::from_value(serde_json::json!({
+      "q": "skywalker",
+      "filter": "age > 18",
+      "limit": 10,
+      "attributesToHighlight": ["bio"],
+      "facets": ["planet"],
+    }))
+    .unwrap();
+
+    let body = serde_json::to_value(meili.search_with("employees", &params)).unwrap();
+
+    assert_eq!(body["q"], serde_json::json!("skywalker"));
+    assert_eq!(body["limit"], serde_json::json!(10));
+    assert_eq!(body["attributesToHighlight"], serde_json::json!(["bio"]));
+    assert_eq!(body["facetsDistribution"], serde_json::json!(["planet"]));
+    assert_eq!(body.get("offset"), Some(&serde_json::Value::Null));
+  }
+
+  #[test]
+  fn search_params_unknown() {
+    let params = serde_json::from_value::<SearchParams>(serde_json::json!({ "q": "skywalker", "unknown": true }));
+
+    assert!(params.is_err());
+  }
+
+  #[test]
+  fn get_query_string() {
+    let meili = MeiliMelo::new("");
+    let query = meili
+      .search("employees")
+      .query("skywalker")
+      .limit(10)
+      .retrieve(&["firstname", "lastname"])
+      .facets(FacetBuilder::new("company", "ACME").build())
+      .show_matches_position(true);
+
+    let parameters = query_string(&serde_json::to_value(query).unwrap());
+    let parameter = |name: &str| {
+      parameters
+        .iter()
+        .find(|(parameter, _)| parameter == name)
+        .map(|(_, value)| value.as_str())
+    };
+
+    assert_eq!(parameter("q"), Some("skywalker"));
+    assert_eq!(parameter("limit"), Some("10"));
+    assert_eq!(parameter("attributesToRetrieve"), Some("firstname,lastname"));
+    assert_eq!(parameter("facetFilters"), Some(r#"[["company:ACME"]]"#));
+    assert_eq!(parameter("showMatchesPosition"), Some("true"));
+    assert_eq!(parameter("offset"), None);
+  }
+
+  #[test]
+  fn page() {
+    let meili = MeiliMelo::new("");
+    let query = meili.search("employees").page(2).hits_per_page(10);
+
+    assert_eq!(query.page, Some(2));
+    assert_eq!(query.hits_per_page, Some(10));
+    assert!(query.validate().is_ok());
+
+    let body = serde_json::to_value(query).unwrap();
+    assert_eq!(body["page"], serde_json::json!(2));
+    assert_eq!(body["hitsPerPage"], serde_json::json!(10));
+  }
+
+  #[test]
+  fn max_limit() {
+    let meili = MeiliMelo::new("");
+
+    let mut query = meili.search("employees").limit(10000).max_limit(100);
+    query.clamp_limits();
+    assert_eq!(serde_json::to_value(&query).unwrap()["limit"], serde_json::json!(100));
+
+    let mut query = meili.search("employees").max_limit(100).hits_per_page(10000);
+    query.clamp_limits();
+    assert_eq!(query.hits_per_page, Some(100));
+
+    let mut query = meili.search("employees").limit(10).max_limit(100);
+    query.clamp_limits();
+    assert_eq!(query.limit, Some(10));
+
+    let body = serde_json::to_value(meili.search("employees").max_limit(100)).unwrap();
+    assert_eq!(body.get("maxLimit"), None);
+    assert_eq!(body.get("max_limit"), None);
+  }
+
+  #[test]
+  fn zero_hits() {
+    let meili = MeiliMelo::new("");
+
+    let mut query = meili.search("employees").limit(20);
+    query.zero_hits();
+    assert_eq!(query.limit, Some(0));
+    assert!(query.validate().is_ok());
+
+    let mut query = meili.search("employees").page(2);
+    query.zero_hits();
+    assert_eq!(query.limit, None);
+    assert_eq!(query.hits_per_page, Some(0));
+    assert!(query.validate().is_ok());
+  }
+
+  #[test]
+  fn page_conflicts_with_offset() {
+    let meili = MeiliMelo::new("");
+
+    assert!(matches!(
+      meili.search("employees").limit(10).page(2).validate(),
+      Err(Error::ValidationError(_))
+    ));
+    assert!(matches!(
+      meili.search("employees").offset(10).hits_per_page(10).validate(),
+      Err(Error::ValidationError(_))
+    ));
+  }
+
+  #[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "tokio-runtime", feature = "async-std-runtime")
+  ))]
+  #[test]
+  fn deadline() {
+    let meili = MeiliMelo::new("");
+    let deadline = std::time::Instant::now();
+    let query = meili.search("employees").deadline(deadline);
+
+    assert_eq!(query.deadline, Some(deadline));
+  }
+
+  #[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "tokio-runtime", feature = "async-std-runtime")
+  ))]
+  #[tokio::test]
+  async fn deadline_exceeded() {
+    // The deadline is checked before anything is sent, so no instance is needed
+    let meili = MeiliMelo::new("http://localhost:7700");
+    let result = meili
+      .search("employees")
+      .deadline(std::time::Instant::now() - std::time::Duration::from_secs(1))
+      .ids("id")
+      .await;
+
+    assert!(matches!(result, Err(Error::DeadlineExceeded)));
+  }
+
+  #[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "tokio-runtime", feature = "async-std-runtime")
+  ))]
+  #[tokio::test]
+  async fn with_deadline() {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_millis(10);
+    let result = super::with_deadline(deadline, futures::future::pending::<Result<(), Error>>()).await;
+
+    assert!(matches!(result, Err(Error::DeadlineExceeded)));
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(60);
+    let result = super::with_deadline(deadline, async {
+      Err::<(), _>(Error::ValidationError("invalid".to_string()))
+    })
+    .await;
+
+    assert!(matches!(result, Err(Error::ValidationError(_))));
+  }
+
+  #[test]
+  fn query_error_display() {
+    let error: QueryError = serde_json::from_value(serde_json::json!({
+      "errorType": "invalid_request_error",
+      "errorCode": "index_not_found",
+      "message": "Index `employees` not found.",
+      "errorLink": "https://docs.meilisearch.com/errors#index_not_found",
+    }))
+    .unwrap();
+
+    let error = Error::from(error);
+
+    assert_eq!(
+      error.to_string(),
+      "meilisearch query error: Index `employees` not found. (index_not_found, see https://docs.meilisearch.com/errors#index_not_found)"
+    );
+
+    let source = std::error::Error::source(&error).unwrap();
+
+    assert_eq!(
+      source.downcast_ref::<QueryError>().map(|error| error.code.as_str()),
+      Some("index_not_found")
+    );
+  }
+
+  #[test]
+  fn crop_negative_length() {
+    let meili = MeiliMelo::new("");
+
+    assert!(meili
+      .search("employees")
+      .crop(&[Crop::At("bio", 10)])
+      .validate()
+      .is_ok());
+    assert!(matches!(
+      meili
+        .search("employees")
+        .crop(&[Crop::Attr("name"), Crop::At("bio", -1)])
+        .validate(),
+      Err(Error::ValidationError(_))
+    ));
+    assert!(matches!(
+      meili.search("employees").crop_length(-1).validate(),
+      Err(Error::ValidationError(_))
+    ));
+  }
+
+  #[test]
+  fn matching_strategy() {
+    let meili = MeiliMelo::new("");
+    let body = serde_json::to_value(meili.search("employees").matching_strategy(MatchingStrategy::Frequency)).unwrap();
+
+    assert_eq!(body["matchingStrategy"], serde_json::json!("frequency"));
+  }
+
+  #[test]
+  fn show_matches_position() {
+    let meili = MeiliMelo::new("");
+    let body = serde_json::to_value(meili.search("employees")).unwrap();
+
+    assert_eq!(body.get("showMatchesPosition"), None);
+    assert_eq!(body.get("matches"), None);
+
+    let body = serde_json::to_value(meili.search("employees").show_matches_position(true)).unwrap();
+
+    assert_eq!(body["showMatchesPosition"], serde_json::json!(true));
+  }
+
+  #[test]
+  fn show_ranking_score() {
+    let meili = MeiliMelo::new("");
+    let body = serde_json::to_value(meili.search("employees").show_ranking_score(true)).unwrap();
+
+    assert_eq!(body["showRankingScore"], serde_json::json!(true));
+  }
+
+  #[test]
+  fn ranking_score_threshold() {
+    let meili = MeiliMelo::new("");
+    let query = meili.search("employees").ranking_score_threshold(0.5);
+
+    assert_eq!(query.ranking_score_threshold, Some(0.5));
+    assert!(query.validate().is_ok());
+    assert!(matches!(
+      meili.search("employees").ranking_score_threshold(1.5).validate(),
+      Err(Error::ValidationError(_))
+    ));
+  }
+
+  #[test]
+  fn clear() {
+    let meili = MeiliMelo::new("");
+    let base = meili
+      .search("employees")
+      .filters("age > 18")
+      .facets(FacetBuilder::new("company", "ACME").build())
+      .limit(10)
+      .offset(20);
+
+    let query = base.clone().clear_filters().clear_facets().clear_limit().clear_offset();
+
+    assert_eq!(query.filters, None);
+    assert_eq!(query.facets, None);
+    assert_eq!(query.limit, None);
+    assert_eq!(query.offset, None);
+    assert_eq!(base.filters.as_deref(), Some("age > 18"));
+    assert_eq!(base.limit, Some(10));
+  }
+
+  #[test]
+  fn with_key() {
+    let meili = MeiliMelo::new("").with_secret_key("master");
+    let query = meili.search("employees").with_key("tenant");
+
+    assert_eq!(query.key, Some("tenant"));
   }
 
   #[test]
@@ -347,7 +1810,29 @@ mod tests {
     let meili = MeiliMelo::new("");
     let query = meili.search("employees").query("skywalker");
 
-    assert_eq!(query.query, Some("skywalker"));
+    assert_eq!(query.query, QueryText::Text("skywalker"));
+
+    let text = "skywalker".to_string();
+    let query = meili.search("employees").query(&text);
+
+    assert_eq!(query.query, QueryText::Text("skywalker"));
+  }
+
+  #[test]
+  fn query_text() {
+    let meili = MeiliMelo::new("");
+
+    let body = serde_json::to_value(meili.search("employees")).unwrap();
+    assert_eq!(body.get("q"), None);
+
+    let body = serde_json::to_value(meili.search("employees").query(QueryText::Null)).unwrap();
+    assert_eq!(body.get("q"), Some(&serde_json::Value::Null));
+
+    let body = serde_json::to_value(meili.search("employees").query(QueryText::Empty)).unwrap();
+    assert_eq!(body.get("q"), Some(&serde_json::json!("")));
+
+    let body = serde_json::to_value(meili.search("employees").query("skywalker")).unwrap();
+    assert_eq!(body.get("q"), Some(&serde_json::json!("skywalker")));
   }
 
   #[test]
@@ -355,7 +1840,52 @@ mod tests {
     let meili = MeiliMelo::new("");
     let query = meili.search("employees").filters("name = skywalker");
 
-    assert_eq!(query.filters, Some("name = skywalker"));
+    assert_eq!(query.filters.as_deref(), Some("name = skywalker"));
+  }
+
+  #[test]
+  fn geo_filters() {
+    let meili = MeiliMelo::new("");
+
+    let query = meili.search("restaurants").geo_radius(48.8566, 2.3522, 2000.0);
+    assert_eq!(query.filters.as_deref(), Some("_geoRadius(48.8566, 2.3522, 2000)"));
+
+    let query = meili
+      .search("restaurants")
+      .geo_bounding_box(GeoPoint::new(48.9, 2.4), GeoPoint::new(48.8, 2.3));
+    assert_eq!(
+      query.filters.as_deref(),
+      Some("_geoBoundingBox([48.9, 2.4], [48.8, 2.3])")
+    );
+
+    let query = meili
+      .search("restaurants")
+      .filters("type = bistro OR type = brasserie")
+      .geo_radius(48.8566, 2.3522, 500.0);
+    assert_eq!(
+      query.filters.as_deref(),
+      Some("(type = bistro OR type = brasserie) AND _geoRadius(48.8566, 2.3522, 500)")
+    );
+  }
+
+  #[test]
+  fn filter_builder() {
+    let meili = MeiliMelo::new("");
+    let query = meili
+      .search("employees")
+      .filters(FilterBuilder::new("company", "ACME").and("roles", "Tech").build());
+
+    assert_eq!(query.filters.as_deref(), Some(r#"company = "ACME" AND roles = "Tech""#));
+  }
+
+  #[test]
+  #[cfg(not(feature = "legacy-filters"))]
+  fn filters_body() {
+    let meili = MeiliMelo::new("");
+    let body = serde_json::to_value(meili.search("employees").filters("name = skywalker")).unwrap();
+
+    assert_eq!(body.get("filter"), Some(&serde_json::json!("name = skywalker")));
+    assert_eq!(body.get("filters"), None);
   }
 
   #[test]
@@ -386,12 +1916,29 @@ mod tests {
     );
   }
 
+  #[test]
+  fn facets_additive() {
+    let meili = MeiliMelo::new("");
+    let query = meili
+      .search("employees")
+      .facets(FacetBuilder::new("company", "ACME").or("company", "Corp").build())
+      .facets(FacetBuilder::new("department", "IT").build());
+
+    assert_eq!(
+      query.facets,
+      Some(vec![
+        vec!["company:ACME".to_string(), "company:Corp".to_string()],
+        vec!["department:IT".to_string()]
+      ])
+    );
+  }
+
   #[test]
   fn retrieve() {
     let meili = MeiliMelo::new("");
     let query = meili.search("employees").retrieve(&["firstname", "lastname"]);
 
-    assert_eq!(query.retrieve, Some(&["firstname", "lastname"] as &[&str]))
+    assert_eq!(query.retrieve, Some(vec!["firstname", "lastname"]))
   }
 
   #[test]
@@ -399,7 +1946,7 @@ mod tests {
     let meili = MeiliMelo::new("");
     let query = meili.search("employees").distribution(&["age"]);
 
-    assert_eq!(query.distribution, Some(&["age"] as &[&str]));
+    assert_eq!(query.distribution, Some(vec!["age"]));
   }
 
   #[test]
@@ -415,6 +1962,70 @@ mod tests {
     );
   }
 
+  #[test]
+  fn sort() {
+    let meili = MeiliMelo::new("");
+    let query = meili
+      .search("employees")
+      .sort(&[Sort::Asc("lastname"), Sort::Desc("age")]);
+
+    assert_eq!(
+      query.sort,
+      Some(vec!["lastname:asc".to_string(), "age:desc".to_string()])
+    );
+
+    let body = serde_json::to_value(&query).unwrap();
+    assert_eq!(body["sort"], serde_json::json!(["lastname:asc", "age:desc"]));
+
+    let body = serde_json::to_value(meili.search("employees")).unwrap();
+    assert_eq!(body.get("sort"), None);
+
+    let query = meili
+      .search("employees")
+      .sort(&[Sort::Asc("lastname")])
+      .sort(&[Sort::Desc("age")]);
+
+    assert_eq!(
+      query.sort,
+      Some(vec!["lastname:asc".to_string(), "age:desc".to_string()])
+    );
+  }
+
+  #[test]
+  fn geo_sort() {
+    let meili = MeiliMelo::new("");
+    let query = meili
+      .search("restaurants")
+      .geo_sort(48.8566, 2.3522, true)
+      .sort(&[Sort::Desc("rating")])
+      .geo_sort(0.0, -1.5, false);
+
+    let body = serde_json::to_value(query).unwrap();
+
+    assert_eq!(
+      body["sort"],
+      serde_json::json!([
+        "_geoPoint(48.8566, 2.3522):asc",
+        "rating:desc",
+        "_geoPoint(0, -1.5):desc"
+      ])
+    );
+  }
+
+  #[test]
+  fn highlight_crop_body() {
+    let meili = MeiliMelo::new("");
+    let query = meili
+      .search("employees")
+      .crop(&[Crop::At("bio", 10)])
+      .highlight(&["bio"]);
+
+    let body = serde_json::to_value(query).unwrap();
+
+    assert_eq!(body.get("attributesToCrop"), Some(&serde_json::json!(["bio:10"])));
+    assert_eq!(body.get("attributesToHighlight"), Some(&serde_json::json!(["bio"])));
+  }
+
   #[test]
   fn crop_length() {
     let meili = MeiliMelo::new("");
@@ -423,11 +2034,56 @@ mod tests {
     assert_eq!(query.crop_length, Some(32));
   }
 
+  #[test]
+  fn crop_marker() {
+    let meili = MeiliMelo::new("");
+    let body = serde_json::to_value(meili.search("employees")).unwrap();
+
+    assert_eq!(body.get("cropMarker"), None);
+
+    let query = meili.search("employees").crop_length(10).crop_marker("");
+    let body = serde_json::to_value(query).unwrap();
+
+    assert_eq!(body["cropMarker"], serde_json::json!(""));
+    assert_eq!(body["cropLength"], serde_json::json!(10));
+  }
+
   #[test]
   fn highlight() {
     let meili = MeiliMelo::new("");
     let query = meili.search("employees").highlight(&["overview", "bio"]);
 
-    assert_eq!(query.highlight, Some(&["overview", "bio"] as &[&str]));
+    assert_eq!(query.highlight, Some(vec!["overview", "bio"]));
+  }
+
+  #[test]
+  fn highlight_tags() {
+    let meili = MeiliMelo::new("");
+    let body = serde_json::to_value(meili.search("employees")).unwrap();
+
+    assert_eq!(body.get("highlightPreTag"), None);
+    assert_eq!(body.get("highlightPostTag"), None);
+
+    let query = meili.search("employees").highlight_tags("<mark>", "</mark>");
+    let body = serde_json::to_value(query).unwrap();
+
+    assert_eq!(body["highlightPreTag"], serde_json::json!("<mark>"));
+    assert_eq!(body["highlightPostTag"], serde_json::json!("</mark>"));
+  }
+
+  #[test]
+  fn distinct() {
+    let meili = MeiliMelo::new("");
+    let query = meili.search("employees").distinct("lastname");
+
+    assert_eq!(query.distinct, Some("lastname"));
+  }
+
+  #[test]
+  fn show_ranking_score_details() {
+    let meili = MeiliMelo::new("");
+    let query = meili.search("employees").show_ranking_score_details(true);
+
+    assert_eq!(query.ranking_score_details, Some(true));
   }
 }
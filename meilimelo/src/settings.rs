@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use reqwest::{Method, StatusCode};
+use serde::Deserialize;
+
+use crate::{
+  client::JsonBody,
+  prelude::*,
+  search::QueryError,
+  updates::{Enqueued, Update, UpdateKind},
+  Error,
+};
+
+/// Settings of a MeiliSearch index
+///
+/// When updating settings, only the attributes that are set are sent to
+/// MeiliSearch, and the others are left untouched.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Settings {
+  #[serde(rename = "rankingRules", skip_serializing_if = "Option::is_none")]
+  pub ranking_rules: Option<Vec<String>>,
+  #[serde(rename = "distinctAttribute", skip_serializing_if = "Option::is_none")]
+  pub distinct_attribute: Option<String>,
+  #[serde(rename = "searchableAttributes", skip_serializing_if = "Option::is_none")]
+  pub searchable_attributes: Option<Vec<String>>,
+  #[serde(rename = "displayedAttributes", skip_serializing_if = "Option::is_none")]
+  pub displayed_attributes: Option<Vec<String>>,
+  #[serde(rename = "stopWords", skip_serializing_if = "Option::is_none")]
+  pub stop_words: Option<Vec<String>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub synonyms: Option<HashMap<String, Vec<String>>>,
+  #[serde(rename = "attributesForFaceting", skip_serializing_if = "Option::is_none")]
+  pub attributes_for_faceting: Option<Vec<String>>,
+}
+
+pub(crate) async fn get<R>(meili: &MeiliMelo<'_>, uid: &str) -> Result<R, Error>
+where
+  R: for<'de> Deserialize<'de>,
+{
+  let response = meili
+    .request(Method::GET, &format!("/indexes/{}/settings", uid))
+    .send()
+    .await
+    .map_err(|err| Error::UpstreamError(err))?;
+
+  match response.status() {
+    StatusCode::OK => meili.json::<R>(response).await,
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(Error::InvalidQuery(error))
+    }
+  }
+}
+
+pub(crate) async fn update(meili: &MeiliMelo<'_>, uid: &str, settings: &Settings) -> Result<Update, Error> {
+  let response = meili
+    .request(Method::POST, &format!("/indexes/{}/settings", uid))
+    .json_body(meili, settings)
+    .send()
+    .await
+    .map_err(|err| Error::UpstreamError(err))?;
+
+  match response.status() {
+    status if status.is_success() => Ok(
+      meili
+        .json::<Enqueued>(response)
+        .await?
+        .into_update(uid, UpdateKind::Settings),
+    ),
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(Error::InvalidQuery(error))
+    }
+  }
+}
+
+pub(crate) async fn reset(meili: &MeiliMelo<'_>, uid: &str) -> Result<Update, Error> {
+  let response = meili
+    .request(Method::DELETE, &format!("/indexes/{}/settings", uid))
+    .send()
+    .await
+    .map_err(|err| Error::UpstreamError(err))?;
+
+  match response.status() {
+    status if status.is_success() => Ok(
+      meili
+        .json::<Enqueued>(response)
+        .await?
+        .into_update(uid, UpdateKind::Settings),
+    ),
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(Error::InvalidQuery(error))
+    }
+  }
+}
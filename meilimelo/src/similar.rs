@@ -0,0 +1,42 @@
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+
+use crate::{client::JsonBody, prelude::*, search::QueryError, Error};
+
+#[derive(Debug, Serialize)]
+struct SimilarQuery<'a> {
+  id: &'a str,
+  embedder: &'a str,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  limit: Option<i64>,
+}
+
+pub(crate) async fn get<R>(
+  meili: &MeiliMelo<'_>,
+  index: &str,
+  id: &str,
+  embedder: &str,
+  limit: Option<i64>,
+) -> Result<Results<R>, Error>
+where
+  for<'de> R: Deserialize<'de>,
+{
+  let body = SimilarQuery { id, embedder, limit };
+
+  let response = meili
+    .request(Method::POST, &format!("/indexes/{}/similar", index))
+    .json_body(meili, &body)
+    .send()
+    .await
+    .map_err(|err| Error::UpstreamError(err))?;
+
+  match response.status() {
+    StatusCode::OK => meili.json::<Results<R>>(response).await,
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(Error::InvalidQuery(error))
+    }
+  }
+}
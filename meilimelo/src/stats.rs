@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use reqwest::{Method, StatusCode};
+
+use crate::{prelude::*, search::QueryError, Error};
+
+/// Statistics about a MeiliSearch index
+#[derive(Debug, Deserialize)]
+pub struct IndexStats {
+  #[serde(rename = "numberOfDocuments")]
+  pub number_of_documents: i64,
+  /// Whether the index is currently being updated
+  #[serde(rename = "isIndexing")]
+  pub is_indexing: bool,
+  /// Number of documents containing each attribute
+  #[serde(rename = "fieldsDistribution", alias = "fieldDistribution")]
+  pub field_distribution: HashMap<String, i64>,
+}
+
+/// Statistics about a MeiliSearch instance
+#[derive(Debug, Deserialize)]
+pub struct Stats {
+  /// Size of the database, in bytes
+  #[serde(rename = "databaseSize")]
+  pub database_size: i64,
+  /// Date of the last update to the database, if any
+  #[serde(rename = "lastUpdate")]
+  pub last_update: Option<String>,
+  /// Statistics of each index, keyed by their unique ID
+  pub indexes: HashMap<String, IndexStats>,
+}
+
+pub(crate) async fn global(meili: &MeiliMelo<'_>) -> Result<Stats, Error> {
+  let response = meili
+    .request(Method::GET, "/stats")
+    .send()
+    .await
+    .map_err(|err| Error::UpstreamError(err))?;
+
+  match response.status() {
+    StatusCode::OK => meili.json::<Stats>(response).await,
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(Error::InvalidQuery(error))
+    }
+  }
+}
+
+pub(crate) async fn index(meili: &MeiliMelo<'_>, uid: &str) -> Result<IndexStats, Error> {
+  let response = meili
+    .request(Method::GET, &format!("/indexes/{}/stats", uid))
+    .send()
+    .await
+    .map_err(|err| Error::UpstreamError(err))?;
+
+  match response.status() {
+    StatusCode::OK => meili.json::<IndexStats>(response).await,
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(Error::InvalidQuery(error))
+    }
+  }
+}
@@ -0,0 +1,526 @@
+#[cfg(all(
+  not(target_arch = "wasm32"),
+  any(feature = "tokio-runtime", feature = "async-std-runtime")
+))]
+use std::time::{Duration, Instant};
+
+#[cfg(all(
+  not(target_arch = "wasm32"),
+  any(feature = "tokio-runtime", feature = "async-std-runtime")
+))]
+use futures::{stream, Stream};
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+use crate::{prelude::*, search::QueryError, Error};
+
+/// Descriptor for an asynchronous upstream operation
+#[derive(Debug, Clone)]
+pub struct Update {
+  pub id: i64,
+  /// Index the operation was performed on
+  pub index: String,
+  /// Kind of the operation
+  pub kind: UpdateKind,
+  /// Status of the operation when it was enqueued, if reported by MeiliSearch
+  pub status: Option<Status>,
+  /// Type of the operation as reported by MeiliSearch, such as `documentAdditionOrUpdate`
+  pub task_type: Option<String>,
+  /// Date at which the operation was enqueued, if reported by MeiliSearch
+  pub enqueued_at: Option<String>,
+}
+
+impl Update {
+  /// Get the processing status of the operation
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let meili = MeiliMelo::new("host");
+  /// let update = meili.delete_document("employees", "lskywalker").await.unwrap();
+  /// let status = update.status(&meili).await.unwrap();
+  ///
+  /// println!("{:?} on {}: {:?}", update.kind, update.index, status.status);
+  /// # }
+  /// ```
+  pub async fn status(&self, meili: &MeiliMelo<'_>) -> Result<UpdateStatus, Error> {
+    get(meili, &self.index, self.id).await
+  }
+
+  /// Watch the processing status of the operation until it is finished
+  ///
+  /// The status is polled every `interval`, and every snapshot is yielded,
+  /// the last one being either processed, failed or canceled. The stream also ends
+  /// after yielding an error.
+  ///
+  /// This requires either the `tokio-runtime` (enabled by default) or the
+  /// `async-std-runtime` feature.
+  ///
+  /// # Arguments
+  ///
+  /// * `meili` - client used to poll the status
+  /// * `interval` - duration to wait between two polls
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use std::time::Duration;
+  /// # use futures::StreamExt;
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let meili = MeiliMelo::new("host");
+  /// let update = meili.delete_document("employees", "lskywalker").await.unwrap();
+  /// let mut statuses = Box::pin(update.watch(&meili, Duration::from_millis(500)));
+  ///
+  /// while let Some(status) = statuses.next().await {
+  ///   println!("{:?}", status.map(|status| status.status));
+  /// }
+  /// # }
+  /// ```
+  #[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "tokio-runtime", feature = "async-std-runtime")
+  ))]
+  pub fn watch<'a>(
+    &'a self,
+    meili: &'a MeiliMelo<'_>,
+    interval: Duration,
+  ) -> impl Stream<Item = Result<UpdateStatus, Error>> + 'a {
+    stream::unfold((true, false), move |(first, done)| async move {
+      if done {
+        return None;
+      }
+
+      if !first {
+        crate::runtime::sleep(interval).await;
+      }
+
+      let status = self.status(meili).await;
+      let done = match status {
+        Ok(ref status) => status.is_terminal(),
+        Err(_) => true,
+      };
+
+      Some((status, (false, done)))
+    })
+  }
+}
+
+/// Kind of an asynchronous upstream operation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpdateKind {
+  /// Documents were added or replaced
+  DocumentsAddition,
+  /// Documents were added or partially updated
+  DocumentsPartial,
+  /// Documents were deleted
+  DocumentsDeletion,
+  /// Documents were edited by a function
+  DocumentsEdit,
+  /// Index settings were updated
+  Settings,
+}
+
+/// Response sent by MeiliSearch when an operation is enqueued
+///
+/// Older versions of MeiliSearch only return the ID of the operation.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Enqueued {
+  #[serde(rename = "updateId", alias = "taskUid")]
+  id: i64,
+  #[serde(rename = "indexUid")]
+  index: Option<String>,
+  status: Option<Status>,
+  #[serde(rename = "type")]
+  task_type: Option<String>,
+  #[serde(rename = "enqueuedAt")]
+  enqueued_at: Option<String>,
+}
+
+impl Enqueued {
+  pub(crate) fn into_update(self, index: &str, kind: UpdateKind) -> Update {
+    Update {
+      id: self.id,
+      index: self.index.unwrap_or_else(|| index.to_string()),
+      kind,
+      status: self.status,
+      task_type: self.task_type,
+      enqueued_at: self.enqueued_at,
+    }
+  }
+}
+
+/// Processing status of an asynchronous upstream operation
+#[derive(Debug, Deserialize)]
+#[serde(from = "RawUpdateStatus")]
+pub struct UpdateStatus {
+  pub id: i64,
+  pub status: Status,
+  /// Type of the operation, with the number of documents it affects
+  pub update_type: Option<UpdateType>,
+  /// Details about the operation, reported once it was processed
+  pub details: Option<UpdateDetails>,
+  pub enqueued_at: Option<String>,
+  pub processed_at: Option<String>,
+  /// Error that caused the operation to fail
+  pub error: Option<QueryError>,
+}
+
+/// Processing status as sent by the different versions of MeiliSearch
+#[derive(Deserialize)]
+struct RawUpdateStatus {
+  #[serde(rename = "updateId", alias = "uid")]
+  id: i64,
+  status: Status,
+  #[serde(rename = "type", default, deserialize_with = "update_type")]
+  update_type: Option<UpdateType>,
+  details: Option<UpdateDetails>,
+  #[serde(rename = "enqueuedAt")]
+  enqueued_at: Option<String>,
+  #[serde(rename = "processedAt", alias = "finishedAt")]
+  processed_at: Option<String>,
+  error: Option<Value>,
+  #[serde(rename = "errorType")]
+  error_type: Option<String>,
+  #[serde(rename = "errorCode")]
+  error_code: Option<String>,
+  #[serde(rename = "errorLink")]
+  error_link: Option<String>,
+}
+
+impl From<RawUpdateStatus> for UpdateStatus {
+  fn from(raw: RawUpdateStatus) -> UpdateStatus {
+    let error = match raw.error {
+      // Older versions of MeiliSearch report the error of a failed operation
+      // as a plain message, next to its type, code and link.
+      Some(Value::String(message)) => match (raw.error_type, raw.error_code) {
+        (Some(kind), Some(code)) => Some(QueryError {
+          kind,
+          code,
+          message,
+          link: raw.error_link.unwrap_or_default(),
+        }),
+        _ => None,
+      },
+
+      Some(error) => serde_json::from_value(error).ok(),
+      None => None,
+    };
+
+    UpdateStatus {
+      id: raw.id,
+      status: raw.status,
+      update_type: raw.update_type,
+      details: raw.details,
+      enqueued_at: raw.enqueued_at,
+      processed_at: raw.processed_at,
+      error,
+    }
+  }
+}
+
+/// Newer versions of MeiliSearch report the type of an operation as a plain
+/// string, without the number of affected documents, which is ignored.
+fn update_type<'de, D>(deserializer: D) -> Result<Option<UpdateType>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  Ok(serde_json::from_value(Value::deserialize(deserializer)?).ok())
+}
+
+/// Stage of the processing of an asynchronous upstream operation
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+  Enqueued,
+  Processing,
+  #[serde(alias = "succeeded")]
+  Processed,
+  Failed,
+  /// The operation was canceled before being processed
+  Canceled,
+}
+
+/// Type of an operation, as reported by MeiliSearch
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateType {
+  /// Name of the type, such as `DocumentsAddition` or `DocumentsDeletion`
+  pub name: String,
+  /// Number of documents affected by a documents operation
+  pub number: Option<i64>,
+}
+
+/// Details reported by MeiliSearch about a processed operation
+#[derive(Debug, Deserialize)]
+pub struct UpdateDetails {
+  /// Primary key of the index, as set or inferred by a documents addition
+  #[serde(rename = "primaryKey")]
+  pub primary_key: Option<String>,
+}
+
+impl UpdateStatus {
+  /// Whether the operation is finished, either processed, failed or canceled
+  pub fn is_terminal(&self) -> bool {
+    matches!(self.status, Status::Processed | Status::Failed | Status::Canceled)
+  }
+
+  /// Error that caused the operation to fail, if it did
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// # use meilimelo::prelude::*;
+  /// #
+  /// # #[tokio::main]
+  /// # async fn main() {
+  /// let meili = MeiliMelo::new("host");
+  /// let update = meili.delete_document("employees", "lskywalker").await.unwrap();
+  /// let status = update.status(&meili).await.unwrap();
+  ///
+  /// if let Some(error) = status.error() {
+  ///   println!("operation failed with {}: {}", error.code, error.message);
+  /// }
+  /// # }
+  /// ```
+  pub fn error(&self) -> Option<&QueryError> {
+    self.error.as_ref()
+  }
+
+  /// Number of documents removed by the operation, if it was a processed deletion
+  pub fn deleted_documents(&self) -> Option<i64> {
+    match self.update_type {
+      Some(ref update_type) if self.status == Status::Processed && update_type.name == "DocumentsDeletion" => {
+        update_type.number
+      }
+      _ => None,
+    }
+  }
+
+  /// Primary key of the index reported by the operation, if it was a documents addition
+  ///
+  /// When documents are first added to an index without primary key,
+  /// MeiliSearch infers it from the documents, which can be checked here
+  /// once the operation was processed.
+  pub fn primary_key(&self) -> Option<&str> {
+    self.details.as_ref().and_then(|details| details.primary_key.as_deref())
+  }
+}
+
+pub(crate) async fn get(meili: &MeiliMelo<'_>, index: &str, id: i64) -> Result<UpdateStatus, Error> {
+  let response = meili
+    .request(Method::GET, &format!("/indexes/{}/updates/{}", index, id))
+    .send()
+    .await
+    .map_err(|err| Error::UpstreamError(err))?;
+
+  match response.status() {
+    StatusCode::OK => meili.json::<UpdateStatus>(response).await,
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(Error::InvalidQuery(error))
+    }
+  }
+}
+
+#[cfg(all(
+  not(target_arch = "wasm32"),
+  any(feature = "tokio-runtime", feature = "async-std-runtime")
+))]
+pub(crate) async fn list(meili: &MeiliMelo<'_>, index: &str) -> Result<Vec<UpdateStatus>, Error> {
+  let response = meili
+    .request(Method::GET, &format!("/indexes/{}/updates", index))
+    .send()
+    .await
+    .map_err(|err| Error::UpstreamError(err))?;
+
+  match response.status() {
+    StatusCode::OK => meili.json::<Vec<UpdateStatus>>(response).await,
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(Error::InvalidQuery(error))
+    }
+  }
+}
+
+/// Interval between two polls of the operations of an index
+#[cfg(all(
+  not(target_arch = "wasm32"),
+  any(feature = "tokio-runtime", feature = "async-std-runtime")
+))]
+const IDLE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+#[cfg(all(
+  not(target_arch = "wasm32"),
+  any(feature = "tokio-runtime", feature = "async-std-runtime")
+))]
+pub(crate) async fn wait_idle(meili: &MeiliMelo<'_>, index: &str, timeout: Duration) -> Result<(), Error> {
+  let deadline = Instant::now() + timeout;
+
+  loop {
+    let pending = list(meili, index).await?.iter().any(|update| !update.is_terminal());
+
+    if !pending {
+      return Ok(());
+    }
+
+    if Instant::now() + IDLE_POLL_INTERVAL > deadline {
+      return Err(Error::DeadlineExceeded);
+    }
+
+    crate::runtime::sleep(IDLE_POLL_INTERVAL).await;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{Enqueued, Status, UpdateKind, UpdateStatus};
+
+  #[test]
+  fn enqueued() {
+    let update = serde_json::from_value::<Enqueued>(serde_json::json!({ "updateId": 1 }))
+      .unwrap()
+      .into_update("employees", UpdateKind::DocumentsAddition);
+
+    assert_eq!(update.id, 1);
+    assert_eq!(update.index, "employees");
+    assert_eq!(update.status, None);
+
+    let update = serde_json::from_value::<Enqueued>(serde_json::json!({
+      "taskUid": 2,
+      "indexUid": "employees",
+      "status": "enqueued",
+      "type": "documentAdditionOrUpdate",
+      "enqueuedAt": "2021-01-01T00:00:00Z",
+    }))
+    .unwrap()
+    .into_update("employees", UpdateKind::DocumentsAddition);
+
+    assert_eq!(update.id, 2);
+    assert_eq!(update.status, Some(Status::Enqueued));
+    assert_eq!(update.task_type.as_deref(), Some("documentAdditionOrUpdate"));
+    assert_eq!(update.enqueued_at.as_deref(), Some("2021-01-01T00:00:00Z"));
+  }
+
+  #[test]
+  fn details() {
+    let status: UpdateStatus = serde_json::from_value(serde_json::json!({
+      "uid": 1,
+      "status": "succeeded",
+      "details": { "receivedDocuments": 10, "indexedDocuments": 10, "primaryKey": "id" },
+      "enqueuedAt": "2021-01-01T00:00:00Z",
+      "finishedAt": "2021-01-01T00:00:01Z",
+    }))
+    .unwrap();
+
+    assert_eq!(status.status, Status::Processed);
+    assert_eq!(status.primary_key(), Some("id"));
+    assert_eq!(status.deleted_documents(), None);
+    assert!(status.error().is_none());
+  }
+
+  #[test]
+  fn deleted_documents() {
+    let status: UpdateStatus = serde_json::from_value(serde_json::json!({
+      "status": "processed",
+      "updateId": 3,
+      "type": { "name": "DocumentsDeletion", "number": 2 },
+      "duration": 0.004_121_236,
+      "enqueuedAt": "2021-03-22T10:15:01.417213427Z",
+      "processedAt": "2021-03-22T10:15:01.424048302Z",
+    }))
+    .unwrap();
+
+    assert_eq!(status.deleted_documents(), Some(2));
+
+    let status: UpdateStatus = serde_json::from_value(serde_json::json!({
+      "status": "processed",
+      "updateId": 1,
+      "type": { "name": "DocumentsAddition", "number": 4 },
+      "duration": 0.076_980_613,
+      "enqueuedAt": "2021-03-22T10:13:24.623944Z",
+      "processedAt": "2021-03-22T10:13:24.703509Z",
+    }))
+    .unwrap();
+
+    assert_eq!(status.deleted_documents(), None);
+  }
+
+  #[test]
+  fn error() {
+    let status: UpdateStatus = serde_json::from_value(serde_json::json!({
+      "uid": 1,
+      "status": "failed",
+      "error": {
+        "message": "The `json` payload provided is malformed.",
+        "code": "malformed_payload",
+        "type": "invalid_request",
+        "link": "https://docs.meilisearch.com/errors#malformed_payload",
+      },
+    }))
+    .unwrap();
+
+    let error = status.error().unwrap();
+
+    assert_eq!(status.status, Status::Failed);
+    assert_eq!(error.code, "malformed_payload");
+    assert_eq!(error.kind, "invalid_request");
+
+    let status: UpdateStatus = serde_json::from_value(serde_json::json!({
+      "updateId": 1,
+      "status": "failed",
+      "error": "document parse error",
+    }))
+    .unwrap();
+
+    assert!(status.error().is_none());
+  }
+
+  #[test]
+  fn legacy_error() {
+    let status: UpdateStatus = serde_json::from_value(serde_json::json!({
+      "status": "failed",
+      "updateId": 0,
+      "type": { "name": "DocumentsAddition", "number": 1 },
+      "error": "missing primary key",
+      "errorType": "invalid_request_error",
+      "errorCode": "missing_primary_key",
+      "errorLink": "https://docs.meilisearch.com/errors#missing_primary_key",
+      "duration": 0.000_243_6,
+      "enqueuedAt": "2021-03-22T10:13:24.623944Z",
+      "processedAt": "2021-03-22T10:13:24.624187Z",
+    }))
+    .unwrap();
+
+    let error = status.error().unwrap();
+
+    assert_eq!(status.status, Status::Failed);
+    assert_eq!(error.message, "missing primary key");
+    assert_eq!(error.code, "missing_primary_key");
+    assert_eq!(error.kind, "invalid_request_error");
+    assert_eq!(error.link, "https://docs.meilisearch.com/errors#missing_primary_key");
+  }
+
+  #[test]
+  fn canceled() {
+    let statuses: Vec<UpdateStatus> = serde_json::from_value(serde_json::json!([
+      { "uid": 1, "status": "canceled", "type": "documentAdditionOrUpdate" },
+      { "uid": 2, "status": "enqueued", "type": "documentAdditionOrUpdate" },
+    ]))
+    .unwrap();
+
+    assert_eq!(statuses[0].status, Status::Canceled);
+    assert!(statuses[0].is_terminal());
+    assert!(!statuses[1].is_terminal());
+  }
+}
@@ -0,0 +1,53 @@
+use reqwest::{Method, StatusCode};
+
+use crate::{prelude::*, search::QueryError, Error};
+
+/// Version of a MeiliSearch instance
+#[derive(Debug, Clone, Deserialize)]
+pub struct Version {
+  /// Version of the MeiliSearch package, such as `0.20.0`
+  #[serde(rename = "pkgVersion")]
+  pub pkg_version: String,
+  /// Hash of the commit the instance was built from
+  #[serde(rename = "commitSha")]
+  pub commit_sha: String,
+  /// Date at which the instance was built
+  #[serde(rename = "buildDate", alias = "commitDate")]
+  pub build_date: String,
+}
+
+pub(crate) async fn get(meili: &MeiliMelo<'_>) -> Result<Version, Error> {
+  let response = meili
+    .request(Method::GET, "/version")
+    .send()
+    .await
+    .map_err(|err| Error::UpstreamError(err))?;
+
+  match response.status() {
+    StatusCode::OK => meili.json::<Version>(response).await,
+
+    _ => {
+      let error = meili.json::<QueryError>(response).await?;
+
+      Err(Error::InvalidQuery(error))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::Version;
+
+  #[test]
+  fn version() {
+    let version: Version = serde_json::from_value(serde_json::json!({
+      "pkgVersion": "0.20.0",
+      "commitSha": "b61f46c3c1d2b6a1fcdd3c1cbf37e0a9ac8d4d29",
+      "buildDate": "2021-03-22T10:13:24.115233113+00:00",
+    }))
+    .unwrap();
+
+    assert_eq!(version.pkg_version, "0.20.0");
+    assert_eq!(version.commit_sha, "b61f46c3c1d2b6a1fcdd3c1cbf37e0a9ac8d4d29");
+  }
+}
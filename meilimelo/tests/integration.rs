@@ -0,0 +1,88 @@
+//! End-to-end tests against a running MeiliSearch instance
+//!
+//! They target MeiliSearch v0.20 and are skipped unless `MEILIMELO_TEST_URL`
+//! points to an instance, for example one started with:
+//!
+//! ```sh
+//! docker run --rm -p 7700:7700 getmeili/meilisearch:v0.20.0
+//! MEILIMELO_TEST_URL=http://localhost:7700 cargo test --test integration
+//! ```
+//!
+//! The secret key of the instance, if any, can be given in `MEILIMELO_TEST_KEY`.
+
+use std::{
+  env,
+  time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use meilimelo::prelude::*;
+
+#[meilimelo::schema]
+struct Employee {
+  id: String,
+  firstname: String,
+  lastname: String,
+}
+
+fn employee(id: &str, firstname: &str, lastname: &str) -> Employee {
+  Employee {
+    id: id.to_string(),
+    firstname: firstname.to_string(),
+    lastname: lastname.to_string(),
+    ..Default::default()
+  }
+}
+
+fn index_name() -> String {
+  let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis();
+
+  format!("meilimelo_test_{}_{}", std::process::id(), now)
+}
+
+#[tokio::test]
+async fn documents_lifecycle() {
+  let host = match env::var("MEILIMELO_TEST_URL") {
+    Ok(host) => host,
+    Err(_) => return,
+  };
+
+  let key = env::var("MEILIMELO_TEST_KEY").ok();
+  let mut meili = MeiliMelo::new(&host);
+
+  if let Some(ref key) = key {
+    meili = meili.with_secret_key(key);
+  }
+
+  let index = index_name();
+  let timeout = Duration::from_secs(30);
+
+  meili.create_index(&index, "Employees").await.unwrap();
+
+  let documents = [
+    employee("lskywalker", "Luke", "Skywalker"),
+    employee("hsolo", "Han", "Solo"),
+  ];
+
+  meili.insert(&index, &documents).await.unwrap();
+  meili.wait_for_index_idle(&index, timeout).await.unwrap();
+
+  let results = meili.search(&index).query("skywalker").run::<Employee>().await.unwrap();
+
+  assert_eq!(results.len(), 1);
+  assert_eq!(results[0].id, "lskywalker");
+
+  let document = meili.get_document::<Employee>(&index, "hsolo").await.unwrap();
+
+  assert_eq!(document.firstname, "Han");
+
+  meili.delete_document(&index, "hsolo").await.unwrap();
+  meili.wait_for_index_idle(&index, timeout).await.unwrap();
+
+  assert!(meili
+    .get_document_opt::<Employee>(&index, "hsolo")
+    .await
+    .unwrap()
+    .is_none());
+
+  meili.delete_index(&index).await.unwrap();
+}
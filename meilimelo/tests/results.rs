@@ -0,0 +1,123 @@
+use meilimelo::prelude::*;
+
+#[test]
+fn facet_distribution_sorted() {
+  let body = serde_json::json!({
+    "hits": [],
+    "query": "",
+    "nbHits": 0,
+    "limit": 20,
+    "offset": 0,
+    "processingTimeMs": 1,
+    "facetDistribution": {
+      "planet": { "Tatooine": 3, "Naboo": 1, "Alderaan": 3 },
+    },
+  });
+
+  let results: Results<serde_json::Value> = serde_json::from_value(body).unwrap();
+
+  assert_eq!(
+    results.facet_distribution_sorted("planet"),
+    vec![("Alderaan".to_string(), 3), ("Tatooine".to_string(), 3), ("Naboo".to_string(), 1)]
+  );
+  assert!(results.facet_distribution_sorted("species").is_empty());
+}
+
+#[test]
+fn page_results() {
+  let body = serde_json::json!({
+    "hits": [{ "id": 1 }],
+    "query": "jedi",
+    "processingTimeMs": 1,
+    "hitsPerPage": 1,
+    "page": 2,
+    "totalPages": 3,
+    "totalHits": 3,
+  });
+
+  let results: Results<serde_json::Value> = serde_json::from_value(body).unwrap();
+
+  assert_eq!(results.page, Some(2));
+  assert_eq!(results.hits_per_page, Some(1));
+  assert_eq!(results.total_pages, Some(3));
+  assert_eq!(results.hits, 3);
+  assert_eq!(results.limit, 0);
+  assert_eq!(results.offset, 0);
+  assert_eq!(results.len(), 1);
+}
+
+#[test]
+fn retain() {
+  let body = serde_json::json!({
+    "hits": [
+      { "id": 1, "planet": "Tatooine" },
+      { "id": 2, "planet": "Naboo" },
+      { "id": 3, "planet": "Tatooine" },
+    ],
+    "query": "",
+    "nbHits": 3,
+    "limit": 20,
+    "offset": 0,
+    "processingTimeMs": 1,
+  });
+
+  let mut results: Results<serde_json::Value> = serde_json::from_value(body).unwrap();
+
+  results.retain(|hit| hit["planet"] == "Tatooine");
+
+  assert_eq!(results.hits, 2);
+  assert_eq!(results.len(), 2);
+  assert_eq!(results[0]["id"], 1);
+  assert_eq!(results[1]["id"], 3);
+}
+
+fn employees(hits: i64) -> Results<serde_json::Value> {
+  let body = serde_json::json!({
+    "hits": [
+      { "id": "lskywalker", "firstname": "Luke" },
+      { "id": "lorgana", "firstname": "Leia" },
+      { "id": "lskywalker", "firstname": "Anakin" },
+    ],
+    "query": "",
+    "nbHits": hits,
+    "limit": 20,
+    "offset": 0,
+    "processingTimeMs": 1,
+  });
+
+  serde_json::from_value(body).unwrap()
+}
+
+#[test]
+fn is_capped() {
+  assert!(!employees(999).is_capped(1000));
+  assert!(employees(1000).is_capped(1000));
+  assert!(employees(1001).is_capped(1000));
+}
+
+#[test]
+fn into_hashmap() {
+  let employees = employees(3).into_hashmap(|hit| hit["id"].as_str().unwrap().to_string());
+
+  assert_eq!(employees.len(), 2);
+  assert_eq!(employees["lorgana"]["firstname"], "Leia");
+  // The last hit sharing a key is kept
+  assert_eq!(employees["lskywalker"]["firstname"], "Anakin");
+}
+
+#[test]
+fn slice_access() {
+  let results = employees(3);
+  let hits: &[serde_json::Value] = results.as_ref();
+
+  assert_eq!(hits.len(), 3);
+  assert_eq!(hits[1]["id"], "lorgana");
+  assert_eq!(results[0]["firstname"], "Luke");
+  assert_eq!(results[2]["firstname"], "Anakin");
+}
+
+#[test]
+#[should_panic]
+fn index_out_of_bounds() {
+  let _ = &employees(3)[3];
+}
@@ -0,0 +1,182 @@
+use meilimelo::prelude::*;
+
+#[meilimelo::schema]
+struct Employee {
+  firstname: String,
+  age: i64,
+  bio: String,
+}
+
+fn results(formatted: serde_json::Value) -> Results<Employee> {
+  let body = serde_json::json!({
+    "hits": [{
+      "firstname": "Luke",
+      "age": 19,
+      "bio": "Luke Skywalker is a Jedi knight from Tatooine",
+      "_formatted": formatted,
+    }],
+    "query": "jedi",
+    "exhaustiveNbHits": false,
+    "nbHits": 1,
+    "limit": 20,
+    "offset": 0,
+    "processingTimeMs": 1,
+  });
+
+  serde_json::from_value(body).unwrap()
+}
+
+#[test]
+fn highlight_and_crop() {
+  let results = results(serde_json::json!({
+    "firstname": "Luke",
+    "age": 19,
+    "bio": "…is a <em>Jedi</em> knight…",
+  }));
+
+  let formatted = results[0].formatted.as_ref().unwrap();
+
+  assert_eq!(formatted.firstname.as_deref(), Some("Luke"));
+  assert_eq!(formatted.age, Some(19));
+  assert_eq!(formatted.bio.as_deref(), Some("…is a <em>Jedi</em> knight…"));
+}
+
+#[test]
+fn formatted_missing_attributes() {
+  let results = results(serde_json::json!({
+    "bio": "…is a <em>Jedi</em> knight…",
+  }));
+
+  let formatted = results[0].formatted.as_ref().unwrap();
+
+  assert_eq!(formatted.firstname, None);
+  assert_eq!(formatted.age, None);
+  assert_eq!(formatted.bio.as_deref(), Some("…is a <em>Jedi</em> knight…"));
+}
+
+#[test]
+fn formatted_mistyped_attributes() {
+  let results = results(serde_json::json!({
+    "age": "19",
+    "bio": "…is a <em>Jedi</em> knight…",
+  }));
+
+  let formatted = results[0].formatted.as_ref().unwrap();
+
+  assert_eq!(formatted.age, None);
+  assert_eq!(formatted.bio.as_deref(), Some("…is a <em>Jedi</em> knight…"));
+}
+
+#[meilimelo::schema]
+struct Planet {
+  name: String,
+  #[serde(rename = "_geo")]
+  location: meilimelo::GeoPoint,
+}
+
+#[test]
+fn field_constants() {
+  assert_eq!(EmployeeFields::FIRSTNAME, "firstname");
+  assert_eq!(EmployeeFields::BIO, "bio");
+  assert_eq!(PlanetFields::NAME, "name");
+  assert_eq!(PlanetFields::LOCATION, "_geo");
+}
+
+#[test]
+fn formatted_accessors() {
+  let results = results(serde_json::json!({
+    "bio": "…is a <em>Jedi</em> knight…",
+  }));
+
+  let bio = results[0].formatted_bio();
+  let firstname = results[0].formatted_firstname();
+
+  assert!(bio.is_formatted());
+  assert_eq!(bio.as_str(), "…is a <em>Jedi</em> knight…");
+  assert!(!firstname.is_formatted());
+  assert_eq!(firstname.as_str(), "Luke");
+}
+
+fn uppercase<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+  D: serde::Deserializer<'de>,
+{
+  let value: String = serde::Deserialize::deserialize(deserializer)?;
+
+  Ok(value.to_uppercase())
+}
+
+#[meilimelo::schema]
+struct Ship {
+  #[serde(rename = "shipName", deserialize_with = "uppercase")]
+  name: String,
+}
+
+#[test]
+fn custom_deserializer() {
+  let ship: Ship = serde_json::from_value(serde_json::json!({
+    "shipName": "Millennium Falcon",
+    "_formatted": { "shipName": "<em>Millennium</em> Falcon" },
+  }))
+  .unwrap();
+
+  assert_eq!(ship.name, "MILLENNIUM FALCON");
+  assert_eq!(
+    ship.formatted.as_ref().unwrap().name.as_deref(),
+    Some("<em>Millennium</em> Falcon")
+  );
+  assert_eq!(ShipFields::NAME, "shipName");
+}
+
+fn default_model() -> String {
+  "YT-1300".to_string()
+}
+
+#[meilimelo::schema]
+struct Freighter {
+  #[serde(default = "default_model", skip_serializing_if = "String::is_empty")]
+  model: String,
+  #[serde(alias = "captain", default)]
+  owner: String,
+}
+
+#[test]
+fn type_specific_attributes() {
+  let freighter: Freighter = serde_json::from_value(serde_json::json!({
+    "captain": "Han Solo",
+    "_formatted": { "model": "<em>YT</em>-1300", "captain": "Han Solo" },
+  }))
+  .unwrap();
+
+  let formatted = freighter.formatted.as_ref().unwrap();
+
+  assert_eq!(freighter.model, "YT-1300");
+  assert_eq!(freighter.owner, "Han Solo");
+  assert_eq!(formatted.model.as_deref(), Some("<em>YT</em>-1300"));
+  assert_eq!(formatted.owner.as_deref(), Some("Han Solo"));
+}
+
+#[meilimelo::schema]
+#[serde(rename_all = "camelCase")]
+struct Pilot {
+  first_name: String,
+  #[serde(rename(serialize = "callSign", deserialize = "callSign"))]
+  call_sign: String,
+}
+
+#[test]
+fn renamed_constants() {
+  assert_eq!(PilotFields::FIRST_NAME, "firstName");
+  assert_eq!(PilotFields::CALL_SIGN, "callSign");
+
+  let pilot: Pilot = serde_json::from_value(serde_json::json!({
+    "firstName": "Wedge",
+    "callSign": "Red Two",
+    "_formatted": { "firstName": "<em>Wedge</em>" },
+  }))
+  .unwrap();
+
+  assert_eq!(pilot.first_name, "Wedge");
+  assert_eq!(pilot.call_sign, "Red Two");
+  assert_eq!(pilot.formatted_first_name().as_str(), "<em>Wedge</em>");
+}